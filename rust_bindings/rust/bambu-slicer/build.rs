@@ -1,10 +1,112 @@
 use std::env;
 use std::path::PathBuf;
 
+/// The platform we're building *for*, as reported by Cargo. Must be read from
+/// `CARGO_CFG_*` / `TARGET`, not `cfg!(...)`, since a build script itself always
+/// runs on the host even when cross-compiling the crate for another target.
+struct TargetPlatform {
+    os: String,
+    arch: String,
+    env: String,
+    triple: String,
+}
+
+impl TargetPlatform {
+    fn from_env() -> Self {
+        TargetPlatform {
+            os: env::var("CARGO_CFG_TARGET_OS").unwrap_or_default(),
+            arch: env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default(),
+            env: env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default(),
+            triple: env::var("TARGET").unwrap_or_default(),
+        }
+    }
+
+    fn is_macos(&self) -> bool {
+        self.os == "macos"
+    }
+
+    fn is_windows(&self) -> bool {
+        self.os == "windows"
+    }
+
+    fn is_msvc(&self) -> bool {
+        self.is_windows() && self.env == "msvc"
+    }
+
+    /// Per-platform directories the linker should search for system libraries,
+    /// beyond the project's own `deps/`/`build/` trees. These are added on top
+    /// of the fixed search paths in `main`, which still include the container's
+    /// absolute deps path and the Debian amd64/arm64 multiarch directories.
+    fn system_lib_dirs(&self) -> Vec<PathBuf> {
+        match self.os.as_str() {
+            "linux" => {
+                // Debian/Ubuntu-style multiarch directories, named after the target
+                // triple's arch/gnu-env rather than being hardcoded to amd64/arm64.
+                let multiarch = format!(
+                    "{}-linux-{}",
+                    self.arch,
+                    if self.env.is_empty() {
+                        "gnu"
+                    } else {
+                        &self.env
+                    }
+                );
+                vec![
+                    PathBuf::from("/usr/local/lib"),
+                    PathBuf::from("/usr/lib"),
+                    PathBuf::from(format!("/usr/lib/{}", multiarch)),
+                ]
+            }
+            "macos" => vec![
+                PathBuf::from("/usr/local/lib"),
+                PathBuf::from("/opt/homebrew/lib"), // Apple Silicon Homebrew prefix
+                PathBuf::from("/opt/homebrew/opt/boost/lib"),
+            ],
+            "windows" => {
+                // MSYS2/MinGW installs under a triple-named prefix; MSVC toolchains
+                // are expected to be resolved via vcpkg instead (see the vcpkg
+                // probing in `main`).
+                vec![
+                    PathBuf::from("C:/msys64/mingw64/lib"),
+                    PathBuf::from(format!("C:/{}/lib", self.triple)),
+                ]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Name of the C++ standard library to link against for this target.
+    fn cpp_runtime_lib(&self) -> &'static str {
+        if self.is_macos() {
+            "c++"
+        } else if self.is_msvc() {
+            // MSVC links its C++ runtime implicitly; nothing to request explicitly.
+            ""
+        } else {
+            "stdc++"
+        }
+    }
+
+    /// macOS frameworks that OCCT's `TKService`/windowing bits link against.
+    fn frameworks(&self) -> &'static [&'static str] {
+        if self.is_macos() {
+            &["CoreFoundation", "CoreServices", "IOKit", "Cocoa"]
+        } else {
+            &[]
+        }
+    }
+}
+
 fn main() {
     println!("cargo:rerun-if-changed=../../../rust_bindings/c_api/slicer_c_api.h");
     println!("cargo:rerun-if-env-changed=BAMBU_BUILD_DIR");
 
+    let platform = TargetPlatform::from_env();
+    println!(
+        "cargo:warning=Building for target {} (os={}, arch={})",
+        platform.triple, platform.os, platform.arch
+    );
+
     // Get build directory from environment or use default
     let build_dir = env::var("BAMBU_BUILD_DIR").unwrap_or_else(|_| {
         let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
@@ -54,14 +156,28 @@ fn main() {
 
     println!("cargo:warning=✓ Bindings generated successfully");
 
+    // Emit safe RAII wrappers (newtype handles + Drop impls) for the opaque
+    // `Slicer*` handle types declared in the header, alongside the raw bindings.
+    // Kept in lockstep with `slicer_c_api.h` via the `rerun-if-changed` above,
+    // instead of requiring a hand-written wrapper for every new entry point.
+    generate_raii_wrappers(&c_api_header, &PathBuf::from(env::var("OUT_DIR").unwrap()));
+
     // Construct absolute paths based on build_dir (which is /BambuStudio/build_rust)
     let build_path = PathBuf::from(&build_dir);
     let project_root = build_path.parent().unwrap(); // /BambuStudio
     let src_dir = project_root.join("src"); // /BambuStudio/src
     let deps_include_dir = project_root.join("deps/build/destdir/usr/local/include");
 
-    // Check if C API library exists
-    let lib_path = PathBuf::from(&build_dir).join("src/libslic3r/liblibslic3r.a");
+    // Check if C API library exists (MSVC names static archives `libslic3r.lib`
+    // instead of the GNU-style `liblibslic3r.a`).
+    let static_lib_name = if platform.is_msvc() {
+        "libslic3r.lib"
+    } else {
+        "liblibslic3r.a"
+    };
+    let lib_path = PathBuf::from(&build_dir)
+        .join("src/libslic3r")
+        .join(static_lib_name);
 
     if !lib_path.exists() {
         println!("cargo:warning=");
@@ -123,8 +239,13 @@ fn main() {
         .include(&src_dir) // for "slic3r/Utils/..." includes (root of src)
         .include(src_dir.join("libslic3r")) // for source headers
         .include(build_path.join("src/libslic3r")) // for generated libslic3r_version.h
-        .include(&deps_include_dir) // for boost, curl, openssl, etc
-        .include("/usr/include") // system headers
+        .include(&deps_include_dir); // for boost, curl, openssl, etc
+
+    if platform.os == "linux" {
+        build.include("/usr/include"); // system headers; not meaningful off Linux
+    }
+
+    build
         // Definitions
         .define("SLIC3R_STATIC", None)
         .define("BBL_RELEASE_TO_PUBLIC", Some("1")) // Assume 1
@@ -167,24 +288,82 @@ fn main() {
         search_paths.push(PathBuf::from(&build_dir).join("src").join(dir));
     }
 
-    // Print all search paths
-    for path in search_paths {
-        if path.exists() {
-            println!("cargo:rustc-link-search=native={}", path.display());
+    // Per-platform system search dirs (multiarch on Linux, Homebrew on macOS,
+    // MSYS2/vcpkg-adjacent on Windows), appended after the fixed list above.
+    search_paths.extend(platform.system_lib_dirs());
+
+    // Probe a handful of external deps through pkg-config on Unix-like targets
+    // (curl, openssl, freetype, fontconfig), or vcpkg on MSVC (curl, openssl,
+    // freetype, boost), so their link paths don't have to be hardcoded
+    // per-distro. Each probe just contributes extra search paths; the explicit
+    // `libs` list below is still what actually requests the link, so a missing
+    // probe simply falls back to the historical hardcoded search paths.
+    for dep in ["libcurl", "openssl", "freetype2", "fontconfig"] {
+        if !platform.is_msvc() {
+            match pkg_config::Config::new().cargo_metadata(false).probe(dep) {
+                Ok(lib) => {
+                    search_paths.extend(lib.link_paths);
+                    println!("cargo:warning=pkg-config found {}", dep);
+                }
+                Err(e) => {
+                    println!("cargo:warning=pkg-config could not find {} ({}), falling back to hardcoded search paths", dep, e);
+                }
+            }
+        }
+    }
+    if platform.is_msvc() {
+        for dep in ["curl", "openssl", "freetype", "boost_system"] {
+            match vcpkg::Config::new().cargo_metadata(false).find_package(dep) {
+                Ok(lib) => {
+                    search_paths.extend(lib.link_paths);
+                    println!("cargo:warning=vcpkg found {}", dep);
+                }
+                Err(e) => {
+                    println!("cargo:warning=vcpkg could not find {} ({})", dep, e);
+                }
+            }
         }
     }
 
-    // Link C++ Standard Library
-    #[cfg(target_os = "macos")]
-    println!("cargo:rustc-link-lib=c++");
+    // Print all search paths, keeping the existing ones around so the
+    // dynamic-link lookup below can probe them for shared-library variants.
+    let existing_search_paths: Vec<PathBuf> =
+        search_paths.into_iter().filter(|p| p.exists()).collect();
+    for path in &existing_search_paths {
+        println!("cargo:rustc-link-search=native={}", path.display());
+    }
+
+    // Link C++ Standard Library (empty on MSVC, which links it implicitly).
+    let cpp_runtime = platform.cpp_runtime_lib();
+    if !cpp_runtime.is_empty() {
+        println!("cargo:rustc-link-lib={}", cpp_runtime);
+    }
+
+    // macOS frameworks required by OCCT's TKService/windowing bits.
+    for framework in platform.frameworks() {
+        println!("cargo:rustc-link-lib=framework={}", framework);
+    }
 
-    #[cfg(not(target_os = "macos"))]
-    println!("cargo:rustc-link-lib=stdc++");
+    // When the `dynamic-link` feature is enabled, prefer the shared (.so/.dylib/.dll)
+    // variant of the heavy internal static stack (libslic3r, CGAL, OCCT, ...) so
+    // iterative rebuilds don't re-link gigabytes of static archives every time.
+    // Falls back to static linking per-lib when the shared variant isn't present,
+    // so a partial dev build (or a release toolchain without shared libs) still works.
+    let dynamic_link = env::var_os("CARGO_FEATURE_DYNAMIC_LINK").is_some();
+    let mut rpath_dirs: Vec<PathBuf> = Vec::new();
 
     // Link libslic3r (Core Slicer)
-    println!("cargo:rustc-link-lib=static=libslic3r");
+    link_lib(
+        "libslic3r",
+        &existing_search_paths,
+        dynamic_link,
+        &mut rpath_dirs,
+    );
 
-    // Link Dependencies
+    // Link Dependencies. The first block is our own heavy static archives
+    // (plus OCCT/CGAL) that benefit the most from dynamic linking; the rest
+    // are thin system deps that are already typically resolved as shared
+    // libraries by the linker regardless of this feature.
     let libs = vec![
         // --- Internal Static Libs ---
         "libslic3r_cgal",
@@ -267,9 +446,201 @@ fn main() {
         "boost_iostreams",
     ];
 
+    const HEAVY_LIBS: &[&str] = &[
+        "libslic3r_cgal",
+        "miniz_static",
+        "semver",
+        "admesh",
+        "clipper",
+        "Clipper2",
+        "nowide",
+        "glu-libtess",
+        "mcut",
+    ];
     for lib in libs {
-        println!("cargo:rustc-link-lib={}", lib);
+        if HEAVY_LIBS.contains(&lib) || lib.starts_with("TK") {
+            link_lib(lib, &existing_search_paths, dynamic_link, &mut rpath_dirs);
+        } else {
+            // Thin system dependency: already resolved as a shared lib by the
+            // linker's default rules, unaffected by the dynamic-link feature.
+            println!("cargo:rustc-link-lib={}", lib);
+        }
+    }
+
+    // Inject an rpath for every directory a shared lib was actually picked up from,
+    // so the resulting `slice_model` binary can find libslic3r.so etc. at runtime
+    // without requiring LD_LIBRARY_PATH to be set.
+    rpath_dirs.sort();
+    rpath_dirs.dedup();
+    for dir in &rpath_dirs {
+        println!("cargo:rustc-link-arg=-Wl,-rpath,{}", dir.display());
     }
 
+    if dynamic_link {
+        println!("cargo:warning=dynamic-link feature enabled: preferring shared libs where available (falling back to static).");
+    }
     println!("cargo:warning=Linking against updated list of dependencies including compiled utils and OCCT/Multimedia libs.");
 }
+
+/// Link a single library, preferring the shared variant when `dynamic_link` is set
+/// and a `.so`/`.dylib`/`.dll` for it exists in one of `search_paths`. Falls back to
+/// static linking (the historical behavior) otherwise. Any directory a shared lib is
+/// resolved from is recorded in `rpath_dirs` so the caller can emit `-rpath` for it.
+fn link_lib(
+    name: &str,
+    search_paths: &[PathBuf],
+    dynamic_link: bool,
+    rpath_dirs: &mut Vec<PathBuf>,
+) {
+    if dynamic_link {
+        if let Some(dir) = find_shared_lib_dir(name, search_paths) {
+            println!("cargo:rustc-link-lib=dylib={}", name);
+            rpath_dirs.push(dir);
+            return;
+        }
+        println!(
+            "cargo:warning=dynamic-link: no shared variant of {} found, falling back to static",
+            name
+        );
+    }
+
+    println!("cargo:rustc-link-lib=static={}", name);
+}
+
+/// Find the directory containing a shared-library variant of `name`
+/// (`lib<name>.so`, `lib<name>.dylib`, or `<name>.dll`), searching `search_paths` in order.
+fn find_shared_lib_dir(name: &str, search_paths: &[PathBuf]) -> Option<PathBuf> {
+    let candidates = [
+        format!("lib{}.so", name),
+        format!("lib{}.dylib", name),
+        format!("{}.dll", name),
+    ];
+
+    for dir in search_paths {
+        for candidate in &candidates {
+            if dir.join(candidate).exists() {
+                return Some(dir.clone());
+            }
+        }
+    }
+    None
+}
+
+/// A C function signature, as scraped out of `slicer_c_api.h`.
+struct CFunctionSig {
+    name: String,
+    /// Raw parameter types, in declaration order (e.g. `"SlicerContext*"`).
+    param_types: Vec<String>,
+}
+
+/// Very small, deliberately non-exhaustive scraper for opaque handle types
+/// (`typedef struct SlicerFoo SlicerFoo;`) and function prototypes
+/// (`RetType slicer_name(Args...);`) in the C API header. This is not a real
+/// C parser - it is only expected to understand the narrow subset of
+/// declaration styles this header is written in, which is enough to pair up
+/// each handle's constructor/destructor automatically.
+fn scrape_header(header_src: &str) -> (Vec<String>, Vec<CFunctionSig>) {
+    let opaque_type_re =
+        regex_lite::Regex::new(r"typedef\s+struct\s+(Slicer\w+)\s+\1\s*;").unwrap();
+    let mut opaque_types: Vec<String> = opaque_type_re
+        .captures_iter(header_src)
+        .map(|c| c[1].to_string())
+        .collect();
+    opaque_types.sort();
+    opaque_types.dedup();
+
+    let fn_re =
+        regex_lite::Regex::new(r"(?m)^\s*[\w\*\s]+?\b(slicer_\w+)\s*\(([^;)]*)\)\s*;").unwrap();
+    let functions = fn_re
+        .captures_iter(header_src)
+        .map(|c| {
+            let name = c[1].to_string();
+            let param_types = c[2]
+                .split(',')
+                .filter_map(|p| {
+                    let p = p.trim();
+                    if p.is_empty() || p == "void" {
+                        return None;
+                    }
+                    // Drop the trailing parameter name, keeping only the type.
+                    let ty = p
+                        .rsplit_once(|c: char| c.is_whitespace() || c == '*')
+                        .map(|(ty, _)| {
+                            ty.trim_end_matches(|c: char| !c.is_alphanumeric() && c != '*')
+                        })
+                        .unwrap_or(p);
+                    Some(ty.to_string())
+                })
+                .collect();
+            CFunctionSig { name, param_types }
+        })
+        .collect();
+
+    (opaque_types, functions)
+}
+
+/// Generate `OUT_DIR/generated_handles.rs`: one newtype wrapper + `Drop` impl
+/// per opaque handle type in `slicer_c_api.h` that has a recognizable
+/// destructor (`..._free`/`..._destroy`, or the legacy bare `slicer_destroy`).
+fn generate_raii_wrappers(header_path: &PathBuf, out_dir: &PathBuf) {
+    let header_src = match std::fs::read_to_string(header_path) {
+        Ok(s) => s,
+        Err(_) => {
+            // Source snapshots without the native tree checked out won't have this
+            // header; emit an empty module rather than failing the whole build.
+            println!("cargo:warning=slicer_c_api.h not found, skipping RAII wrapper generation");
+            std::fs::write(
+                out_dir.join("generated_handles.rs"),
+                "// slicer_c_api.h was not available at build time; nothing generated.\n",
+            )
+            .expect("Failed to write generated_handles.rs");
+            return;
+        }
+    };
+
+    let (opaque_types, functions) = scrape_header(&header_src);
+
+    let mut out = String::new();
+    out.push_str("// @generated by build.rs from slicer_c_api.h. Do not edit by hand.\n\n");
+
+    let mut generated_any = false;
+    for ty in &opaque_types {
+        let ptr_ty = format!("{}*", ty);
+        let destructor = functions.iter().find(|f| {
+            f.param_types.first().map(|t| t.as_str()) == Some(ptr_ty.as_str())
+                && (f.name.ends_with("_free")
+                    || f.name.ends_with("_destroy")
+                    || f.name == "slicer_destroy")
+        });
+
+        let Some(destructor) = destructor else {
+            continue;
+        };
+        generated_any = true;
+
+        // `SlicerContext` -> `Context`
+        let short_name = ty.strip_prefix("Slicer").unwrap_or(ty);
+
+        out.push_str(&format!(
+            "/// Auto-generated RAII handle for `ffi::{ty}`, released via `ffi::{dtor}`.\n\
+             pub(crate) struct {short}Handle(pub(crate) *mut ffi::{ty});\n\n\
+             impl Drop for {short}Handle {{\n\
+             \u{20}   fn drop(&mut self) {{\n\
+             \u{20}       if !self.0.is_null() {{\n\
+             \u{20}           unsafe {{ ffi::{dtor}(self.0) }};\n\
+             \u{20}       }}\n\
+             \u{20}   }}\n\
+             }}\n\n",
+            ty = ty,
+            dtor = destructor.name,
+            short = short_name,
+        ));
+    }
+
+    if !generated_any {
+        out.push_str("// No opaque handle type in slicer_c_api.h had a recognizable destructor.\n");
+    }
+
+    std::fs::write(out_dir.join("generated_handles.rs"), out)
+        .expect("Failed to write generated_handles.rs");
+}
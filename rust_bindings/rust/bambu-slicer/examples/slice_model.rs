@@ -1,256 +1,255 @@
+//! `slice_model` - a general slicer frontend built on the `bambu_slicer` crate.
+//!
+//! Organized around subcommands (`slice`, `presets list`, `info`, `config dump`)
+//! instead of one hand-rolled argument loop, so new entry points can be added
+//! without reimplementing flag parsing each time.
+
 use bambu_slicer::{slice_model, Slicer, SlicerConfig};
-use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use std::path::{Path, PathBuf};
 use std::process;
 
-fn print_usage() {
-    eprintln!("Usage: slice_model [OPTIONS]");
-    eprintln!();
-    eprintln!("Options:");
-    eprintln!("  --model <PATH>         Input model file (STL, 3MF, AMF, OBJ)");
-    eprintln!("  --output <PATH>        Output G-code file");
-    eprintln!("  --printer <NAME>       Printer preset name");
-    eprintln!("  --filament <NAME>      Filament preset name");
-    eprintln!("  --process <NAME>       Process preset name");
-    eprintln!("  --param <KEY=VALUE>    Set config parameter (can be used multiple times)");
-    eprintln!("  --simple               Use simple API (default: builder API)");
-    eprintln!("  --help                 Print this help message");
-    eprintln!();
-    eprintln!("Examples:");
-    eprintln!("  # Using presets:");
-    eprintln!("  slice_model --model cube.3mf --output cube.gcode \\");
-    eprintln!("    --printer \"Bambu Lab A1 0.4 nozzle\" \\");
-    eprintln!("    --filament \"Bambu PLA Basic @BBL A1\" \\");
-    eprintln!("    --process \"0.20mm Standard @BBL A1\"");
-    eprintln!();
-    eprintln!("  # Using custom parameters:");
-    eprintln!("  slice_model --model model.stl --output output.gcode \\");
-    eprintln!("    --param layer_height=0.2 \\");
-    eprintln!("    --param sparse_infill_density=15%");
+#[derive(Parser)]
+#[command(
+    name = "slice_model",
+    version,
+    about = "BambuSlicer command-line frontend"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
 }
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-
-    // Parse arguments
-    let mut model_path: Option<PathBuf> = None;
-    let mut output_path: Option<PathBuf> = None;
-    let mut printer_preset: Option<String> = None;
-    let mut filament_preset: Option<String> = None;
-    let mut process_preset: Option<String> = None;
-    let mut use_simple = false;
-    let mut params: Vec<(String, String)> = Vec::new();
-
-    let mut i = 1;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--help" | "-h" => {
-                print_usage();
-                process::exit(0);
-            }
-            "--model" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --model requires a value");
-                    process::exit(1);
-                }
-                model_path = Some(PathBuf::from(&args[i]));
-            }
-            "--output" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --output requires a value");
-                    process::exit(1);
-                }
-                output_path = Some(PathBuf::from(&args[i]));
-            }
-            "--printer" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --printer requires a value");
-                    process::exit(1);
-                }
-                printer_preset = Some(args[i].clone());
-            }
-            "--filament" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --filament requires a value");
-                    process::exit(1);
-                }
-                filament_preset = Some(args[i].clone());
-            }
-            "--process" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --process requires a value");
-                    process::exit(1);
-                }
-                process_preset = Some(args[i].clone());
-            }
-            "--param" => {
-                i += 1;
-                if i >= args.len() {
-                    eprintln!("Error: --param requires a value");
-                    process::exit(1);
-                }
-                let parts: Vec<&str> = args[i].splitn(2, '=').collect();
-                if parts.len() != 2 {
-                    eprintln!("Error: --param value must be in KEY=VALUE format");
-                    process::exit(1);
-                }
-                params.push((parts[0].to_string(), parts[1].to_string()));
-            }
-            "--simple" => {
-                use_simple = true;
-            }
-            _ => {
-                eprintln!("Error: Unknown option: {}", args[i]);
-                print_usage();
-                process::exit(1);
-            }
+#[derive(Subcommand)]
+enum Command {
+    /// Slice a model and export G-code
+    Slice(SliceArgs),
+    /// Preset-related subcommands
+    Presets {
+        #[command(subcommand)]
+        command: PresetsCommand,
+    },
+    /// Load a model and print bounding box / mesh stats without slicing
+    Info {
+        /// Input model file (STL, 3MF, AMF, OBJ)
+        model: PathBuf,
+    },
+    /// Config-related subcommands
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PresetsCommand {
+    /// Enumerate available printer/filament/process presets
+    List,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// Resolve presets + `--param` overrides and print the effective config as JSON
+    Dump(PresetArgs),
+}
+
+#[derive(Args)]
+struct SliceArgs {
+    /// Input model file (STL, 3MF, AMF, OBJ)
+    #[arg(long)]
+    model: PathBuf,
+
+    /// Output G-code file
+    #[arg(long)]
+    output: PathBuf,
+
+    #[command(flatten)]
+    presets: PresetArgs,
+
+    /// Use the simple one-call API instead of the builder API
+    #[arg(long)]
+    simple: bool,
+}
+
+#[derive(Args, Default)]
+struct PresetArgs {
+    /// Printer preset name
+    #[arg(long)]
+    printer: Option<String>,
+
+    /// Filament preset name
+    #[arg(long)]
+    filament: Option<String>,
+
+    /// Process preset name
+    #[arg(long)]
+    process: Option<String>,
+
+    /// Set a config parameter (KEY=VALUE); may be repeated
+    #[arg(long = "param", value_parser = parse_param)]
+    params: Vec<(String, String)>,
+}
+
+fn parse_param(raw: &str) -> Result<(String, String), String> {
+    let (key, value) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("--param value must be in KEY=VALUE format, got `{}`", raw))?;
+    Ok((key.to_string(), value.to_string()))
+}
+
+impl PresetArgs {
+    fn slicer_config(&self) -> SlicerConfig {
+        SlicerConfig {
+            printer_preset: self.printer.clone(),
+            filament_preset: self.filament.clone(),
+            process_preset: self.process.clone(),
+            custom_config_json: None,
         }
-        i += 1;
     }
 
-    // Validate required arguments
-    if model_path.is_none() {
-        eprintln!("Error: --model is required");
-        print_usage();
-        process::exit(1);
+    fn has_presets(&self) -> bool {
+        self.printer.is_some() || self.filament.is_some() || self.process.is_some()
     }
-    if output_path.is_none() {
-        eprintln!("Error: --output is required");
-        print_usage();
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Slice(args) => run_slice(args),
+        Command::Presets {
+            command: PresetsCommand::List,
+        } => run_presets_list(),
+        Command::Info { model } => run_info(&model),
+        Command::Config {
+            command: ConfigCommand::Dump(args),
+        } => run_config_dump(args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
         process::exit(1);
     }
+}
 
-    let model = model_path.unwrap();
-    let output = output_path.unwrap();
-
+fn run_slice(args: SliceArgs) -> Result<(), Box<dyn Error>> {
     println!("BambuSlicer Rust Bindings v{}", bambu_slicer::get_version());
     println!("BambuStudio version: {}", bambu_slicer::get_bambu_version());
     println!();
-    println!("Model:  {}", model.display());
-    println!("Output: {}", output.display());
+    println!("Model:  {}", args.model.display());
+    println!("Output: {}", args.output.display());
     println!();
 
-    // Run slicing
-    let result = if use_simple {
+    let stats = if args.simple {
         println!("Using simple API...");
-
-        let config = SlicerConfig {
-            printer_preset,
-            filament_preset,
-            process_preset,
-            custom_config_json: None,
-        };
-
-        slice_model(&model, &config, &output)
+        slice_model(&args.model, &args.presets.slicer_config(), &args.output)?
     } else {
         println!("Using builder API...");
 
-        let mut slicer = match Slicer::new() {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("Failed to create slicer: {}", e);
-                process::exit(1);
-            }
-        };
+        let mut slicer = Slicer::new()?;
 
-        // Load model
         print!("Loading model... ");
-        if let Err(e) = slicer.load_model(&model) {
-            eprintln!("\nFailed to load model: {}", e);
-            process::exit(1);
-        }
+        slicer.load_model(&args.model)?;
         println!("OK");
 
-        // Apply configuration
-        if printer_preset.is_some() || filament_preset.is_some() || process_preset.is_some() {
-            let config = SlicerConfig {
-                printer_preset,
-                filament_preset,
-                process_preset,
-                custom_config_json: None,
-            };
-
+        if args.presets.has_presets() {
             print!("Loading presets... ");
-            if let Err(e) = slicer.load_preset(&config) {
-                eprintln!("\nFailed to load presets: {}", e);
-                process::exit(1);
-            }
+            slicer.load_preset(&args.presets.slicer_config())?;
             println!("OK");
         }
 
-        // Apply custom parameters
-        for (key, value) in params {
+        for (key, value) in &args.presets.params {
             print!("Setting {}={}... ", key, value);
-            if let Err(e) = slicer.set_config_param(&key, &value) {
-                eprintln!("\nFailed to set parameter: {}", e);
-                process::exit(1);
-            }
+            slicer.set_config_param(key, value)?;
             println!("OK");
         }
 
-        // Slice
         print!("Slicing... ");
-        if let Err(e) = slicer.slice() {
-            eprintln!("\nFailed to slice: {}", e);
-            process::exit(1);
-        }
-        println!("OK");
+        let outcome = slicer.slice()?;
+        println!("{:?}", outcome.status);
 
-        // Export
-        print!("Exporting G-code... ");
-        if let Err(e) = slicer.export_gcode(&output) {
-            eprintln!("\nFailed to export: {}", e);
-            process::exit(1);
+        for warning in &outcome.warnings {
+            println!("  warning: {}", warning.message);
         }
-        println!("OK");
 
-        // Get stats
-        print!("Reading stats... ");
-        let stats = match slicer.get_stats() {
-            Ok(s) => s,
-            Err(e) => {
-                eprintln!("\nFailed to read stats: {}", e);
-                process::exit(1);
-            }
-        }
+        let Some(stats) = outcome.stats else {
+            println!("Slicing was cancelled; nothing to export.");
+            return Ok(());
+        };
+
+        print!("Exporting G-code... ");
+        slicer.export_gcode(&args.output)?;
         println!("OK");
 
-        Ok(stats)
+        stats
     };
 
-    match result {
-        Ok(stats) => {
-            println!();
-            println!("=== Slicing Complete ===");
-            println!("Print Time:      {}", stats.estimated_print_time);
-            println!("Filament Used:   {:.2} mm", stats.total_used_filament);
-            println!("Extruded Volume: {:.2} mm³", stats.total_extruded_volume);
-            println!("Weight:          {:.2} g", stats.total_weight);
-            println!("Cost:            ${:.2}", stats.total_cost);
-            println!("Tool Changes:    {}", stats.total_toolchanges);
-
-            if !stats.filament_stats.is_empty() {
-                println!();
-                println!("=== Per-Filament Stats ===");
-                for (id, usage) in &stats.filament_stats {
-                    println!("Filament {}: {:.2} mm", id, usage);
-                }
-            }
-
-            println!();
-            println!("Statistics JSON:");
-            match serde_json::to_string_pretty(&stats) {
-                Ok(json) => println!("{}", json),
-                Err(e) => eprintln!("Failed to serialize stats: {}", e),
-            }
-        }
-        Err(e) => {
-            eprintln!("\nSlicing failed: {}", e);
-            process::exit(1);
+    println!();
+    println!("=== Slicing Complete ===");
+    println!("Print Time:      {}", stats.estimated_print_time);
+    println!("Filament Used:   {:.2} mm", stats.total_used_filament);
+    println!("Extruded Volume: {:.2} mm³", stats.total_extruded_volume);
+    println!("Weight:          {:.2} g", stats.total_weight);
+    println!("Cost:            ${:.2}", stats.total_cost);
+    println!("Tool Changes:    {}", stats.total_toolchanges);
+
+    if !stats.filament_stats.is_empty() {
+        println!();
+        println!("=== Per-Filament Stats ===");
+        for (id, usage) in &stats.filament_stats {
+            println!("Filament {}: {:.2} mm", id, usage);
         }
     }
+
+    Ok(())
+}
+
+fn run_presets_list() -> Result<(), Box<dyn Error>> {
+    let slicer = Slicer::new()?;
+    let presets_json = slicer.get_preset_info_json()?;
+    println!("{}", presets_json);
+    Ok(())
+}
+
+fn run_info(model: &Path) -> Result<(), Box<dyn Error>> {
+    let mut slicer = Slicer::new()?;
+    slicer.load_model(model)?;
+
+    let info = slicer.inspect_model()?;
+
+    println!("Model:          {}", model.display());
+    println!("Units:          {}", info.units);
+    println!("Objects:        {}", info.object_count);
+    println!("Triangles:      {}", info.triangle_count);
+    println!("Vertices:       {}", info.vertex_count);
+    println!(
+        "Bounding box:   [{:.2}, {:.2}, {:.2}] - [{:.2}, {:.2}, {:.2}]",
+        info.bounding_box.min[0],
+        info.bounding_box.min[1],
+        info.bounding_box.min[2],
+        info.bounding_box.max[0],
+        info.bounding_box.max[1],
+        info.bounding_box.max[2],
+    );
+    println!("Manifold:       {}", info.is_manifold);
+    println!("Fits build vol: {}", info.fits_build_volume);
+
+    Ok(())
+}
+
+fn run_config_dump(args: PresetArgs) -> Result<(), Box<dyn Error>> {
+    let mut slicer = Slicer::new()?;
+
+    if args.has_presets() {
+        slicer.load_preset(&args.slicer_config())?;
+    }
+
+    for (key, value) in &args.params {
+        slicer.set_config_param(key, value)?;
+    }
+
+    let config_json = slicer.get_config_json()?;
+    println!("{}", config_json);
+    Ok(())
 }
@@ -1,5 +1,6 @@
 //! Error types for the BambuSlicer library
 
+use crate::lint::Diagnostic;
 use thiserror::Error;
 
 /// Errors that can occur when using the slicer
@@ -29,6 +30,9 @@ pub enum SlicerError {
     #[error("Slicing process failed: {0}")]
     ProcessFailed(String),
 
+    #[error("Configuration failed validation: {0:?}")]
+    ConfigValidation(Vec<Diagnostic>),
+
     #[error("G-code export failed: {0}")]
     ExportFailed(String),
 
@@ -2,6 +2,8 @@
 //!
 //! This module contains auto-generated bindings from bindgen.
 //! Use the safe wrappers in the parent module instead of calling these directly.
+//! For the opaque handle types, see [`crate::generated`] for the build-time
+//! generated `Drop`-safe newtypes.
 
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
@@ -0,0 +1,13 @@
+//! Auto-generated RAII wrappers over the raw FFI handles.
+//!
+//! Regenerated by `build.rs` from `slicer_c_api.h` (tracked via the existing
+//! `cargo:rerun-if-changed`), so new opaque handle types get a matching
+//! newtype + `Drop` impl without a manual edit here. See `build.rs` for the
+//! (intentionally small, non-exhaustive) header scraper that produces this.
+//!
+//! [`Slicer`](crate::Slicer) owns its handle through the generated
+//! `ContextHandle` (from `SlicerContext`) instead of a hand-written `Drop`.
+
+use crate::ffi;
+
+include!(concat!(env!("OUT_DIR"), "/generated_handles.rs"));
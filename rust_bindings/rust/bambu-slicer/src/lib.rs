@@ -51,25 +51,130 @@
 //!     .set_config_param("layer_height", "0.2")
 //!     .expect("Failed to set config");
 //!
-//! let stats = slicer.slice().expect("Slicing failed");
+//! let outcome = slicer.slice().expect("Slicing failed");
 //!
 //! slicer
 //!     .export_gcode(Path::new("output.gcode"))
 //!     .expect("Failed to export");
+//!
+//! println!("status: {:?}", outcome.status);
 //! ```
 
 mod error;
 mod ffi;
+mod generated;
+mod lint;
+mod project;
+mod sweep;
 
 pub use error::{Result, SlicerError};
+pub use lint::{ConfigContext, ConfigRule, Diagnostic, Severity};
+pub use project::SlicerProject;
+pub use sweep::{Objective, Strategy, SweepBudget, SweepResult, SweepSpace, Trial};
 
 use serde::{Deserialize, Serialize};
-use std::ffi::{CStr, CString};
+use std::any::Any;
+use std::collections::HashMap;
+use std::ffi::{c_void, CStr, CString};
+use std::os::raw::c_char;
 use std::path::Path;
 use std::ptr;
+use std::sync::Arc;
 
 // Success code from C API (defined here to avoid FFI dependency before build)
 const SLICER_SUCCESS: i32 = 0;
+// `slicer_process` reports cancellation (triggered by `Slicer::cancel`) via
+// this result code, distinct from the positive error codes `from_code`
+// translates for other calls.
+const SLICER_CANCELLED: i32 = -1;
+
+/// Stage of the slicing pipeline a [`SliceEvent`] was emitted from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceStage {
+    Perimeters,
+    Infill,
+    Supports,
+    GcodeExport,
+    /// A stage code the Rust bindings don't recognize yet (forward compatible
+    /// with new stages added to the C API).
+    Unknown(i32),
+}
+
+impl SliceStage {
+    fn from_code(code: i32) -> Self {
+        match code {
+            0 => SliceStage::Perimeters,
+            1 => SliceStage::Infill,
+            2 => SliceStage::Supports,
+            3 => SliceStage::GcodeExport,
+            other => SliceStage::Unknown(other),
+        }
+    }
+}
+
+/// A single progress notification emitted during [`Slicer::slice`].
+///
+/// `seq` increases monotonically for the lifetime of one [`Slicer`], so
+/// callers can detect out-of-order delivery or dropped events.
+#[derive(Debug, Clone)]
+pub struct SliceEvent {
+    pub seq: u64,
+    pub stage: SliceStage,
+    /// Progress within the current stage, in the range `0.0..=1.0`.
+    pub fraction: f32,
+    /// Optional human-readable status message from the C++ core.
+    pub message: Option<String>,
+}
+
+/// Internal state backing [`Slicer::set_progress_callback`]. Boxed and handed
+/// to the C API as an opaque `user_data` pointer; the heap allocation doesn't
+/// move even if the owning [`Slicer`] does, so the pointer stays valid for as
+/// long as the callback is registered.
+struct ProgressState {
+    callback: Box<dyn FnMut(SliceEvent) + Send>,
+    next_seq: u64,
+    /// A panic caught inside the FFI trampoline, re-raised once we're back in
+    /// a normal Rust call stack (unwinding across the FFI boundary is UB).
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+/// `extern "C"` shim invoked by the C++ core for each progress update. Catches
+/// panics from the user's callback rather than letting them unwind through
+/// the FFI boundary; the caught payload is re-raised by [`Slicer::slice`]
+/// after `slicer_process` returns.
+unsafe extern "C" fn progress_trampoline(
+    stage: i32,
+    fraction: f32,
+    message: *const c_char,
+    user_data: *mut c_void,
+) {
+    if user_data.is_null() {
+        return;
+    }
+    let state = &mut *(user_data as *mut ProgressState);
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        let seq = state.next_seq;
+        state.next_seq += 1;
+
+        let message = if message.is_null() {
+            None
+        } else {
+            CStr::from_ptr(message).to_str().ok().map(String::from)
+        };
+
+        (state.callback)(SliceEvent {
+            seq,
+            stage: SliceStage::from_code(stage),
+            fraction,
+            message,
+        });
+    }));
+
+    if let Err(panic) = result {
+        state.panic = Some(panic);
+    }
+}
 
 /// Configuration for the slicer
 #[derive(Debug, Clone, Default)]
@@ -113,12 +218,83 @@ pub struct SlicerStats {
     pub filament_stats: std::collections::HashMap<usize, f64>,
 }
 
+/// Outcome status of a [`Slicer::slice`] call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceStatus {
+    /// Completed with no caveats.
+    Success,
+    /// Completed, but the C++ core made non-fatal adjustments (e.g.
+    /// auto-disabled supports, dropped thin walls, unfilled overhangs) -
+    /// see the accompanying `warnings`.
+    PartialSuccess,
+    /// Aborted by [`Slicer::cancel`] before completion.
+    Cancelled,
+    /// Did not complete. [`Slicer::slice`] never returns an outcome with
+    /// this status; it reports failure through its `Err` case instead.
+    Failed,
+}
+
+/// Structured result of [`Slicer::slice`].
+///
+/// `stats` is populated whenever the process actually ran to completion
+/// ([`SliceStatus::Success`] or [`SliceStatus::PartialSuccess`]); it's `None`
+/// for [`SliceStatus::Cancelled`]. An outright failure is never represented
+/// this way - see the `Err` case of `slice()` instead.
+#[derive(Debug, Clone)]
+pub struct SliceOutcome {
+    pub stats: Option<SlicerStats>,
+    pub status: SliceStatus,
+    /// Non-fatal adjustments the C++ core made along the way. Always empty
+    /// for [`SliceStatus::Success`] and [`SliceStatus::Cancelled`].
+    pub warnings: Vec<Diagnostic>,
+}
+
+/// Axis-aligned bounding box, in the model's native units.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct BoundingBox {
+    pub min: [f64; 3],
+    pub max: [f64; 3],
+}
+
+/// Cheap geometry facts about a loaded model, from [`Slicer::inspect_model`].
+///
+/// Unlike [`slice`](Slicer::slice), computing this doesn't run the full
+/// slicing pipeline - only the mesh-analysis passes the C++ core already
+/// does up front - so it's cheap enough to call before committing to a slice.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ModelInfo {
+    pub bounding_box: BoundingBox,
+    pub triangle_count: u64,
+    pub vertex_count: u64,
+    /// Number of disjoint objects/parts in the loaded model.
+    pub object_count: u32,
+    /// Units the model file declared (e.g. "mm", "inch").
+    pub units: String,
+    /// Whether every part's mesh is watertight/manifold.
+    pub is_manifold: bool,
+    /// Whether the bounding box fits within the selected printer's build volume.
+    pub fits_build_volume: bool,
+}
+
 /// Main slicer context - Builder API
 ///
 /// This provides fine-grained control over the slicing process.
 /// For a simpler API, see [`slice_model`].
 pub struct Slicer {
-    ctx: *mut ffi::SlicerContext,
+    /// Owns the underlying `SlicerContext*` via the generated RAII wrapper,
+    /// so the destructor stays in lockstep with `slicer_c_api.h` instead of
+    /// a hand-maintained `Drop` impl here. Shared (rather than owned
+    /// outright) so a [`CancelHandle`] obtained via
+    /// [`cancel_handle`](Self::cancel_handle) can keep the context alive and
+    /// reach it from another thread even after this `Slicer` is dropped.
+    ctx: Arc<generated::ContextHandle>,
+    progress: Option<Box<ProgressState>>,
+    custom_rules: Vec<Box<dyn ConfigRule + Send>>,
+    fail_on_lint_error: bool,
+    model_path: Option<std::path::PathBuf>,
+    printer_preset: Option<String>,
+    filament_preset: Option<String>,
+    process_preset: Option<String>,
 }
 
 impl Slicer {
@@ -130,7 +306,68 @@ impl Slicer {
                 "Failed to create slicer context".to_string(),
             ));
         }
-        Ok(Slicer { ctx })
+        Ok(Slicer {
+            ctx: Arc::new(generated::ContextHandle(ctx)),
+            progress: None,
+            custom_rules: Vec::new(),
+            fail_on_lint_error: false,
+            model_path: None,
+            printer_preset: None,
+            filament_preset: None,
+            process_preset: None,
+        })
+    }
+
+    /// Register a callback invoked with a [`SliceEvent`] for each progress
+    /// update the C++ core reports during [`slice`](Self::slice).
+    ///
+    /// Replaces any previously registered callback. The closure must not
+    /// panic across the FFI boundary; panics are caught and re-raised by
+    /// `slice()` once control returns to Rust.
+    pub fn set_progress_callback<F>(&mut self, callback: F)
+    where
+        F: FnMut(SliceEvent) + Send + 'static,
+    {
+        let mut state = Box::new(ProgressState {
+            callback: Box::new(callback),
+            // Carry the counter over from a replaced callback so `seq` keeps
+            // increasing for the lifetime of this `Slicer`.
+            next_seq: self.progress.as_ref().map_or(0, |s| s.next_seq),
+            panic: None,
+        });
+        let user_data = state.as_mut() as *mut ProgressState as *mut c_void;
+
+        unsafe {
+            ffi::slicer_set_progress_callback(self.ctx.0, Some(progress_trampoline), user_data);
+        }
+
+        self.progress = Some(state);
+    }
+
+    /// Request that an in-progress [`slice`](Self::slice) call abort as soon
+    /// as the C++ core next checks for cancellation.
+    ///
+    /// `slice()` takes `&mut self` for its entire (blocking) duration, and
+    /// `Slicer` is not [`Sync`], so there's no safe way to call this from a
+    /// second thread while `slice()` is running on the first - despite the
+    /// name, this method only ever helps when something on the *same*
+    /// thread has a reason to cancel before or after that call. To actually
+    /// abort a slice running on another thread, get a [`CancelHandle`] via
+    /// [`cancel_handle`](Self::cancel_handle) before starting it.
+    pub fn cancel(&self) {
+        unsafe { ffi::slicer_cancel(self.ctx.0) };
+    }
+
+    /// Obtain a cloneable, thread-safe handle that can cancel this slicer's
+    /// in-flight [`slice`](Self::slice) call from another thread - the
+    /// mechanism actually meant for aborting long slices, since `Slicer`
+    /// itself can't be reached from another thread while `slice()` is
+    /// running on it. Typically called before moving the `Slicer` onto the
+    /// thread (or blocking task) that will run `slice()`.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle {
+            ctx: self.ctx.clone(),
+        }
     }
 
     /// Load a 3D model from file
@@ -142,13 +379,14 @@ impl Slicer {
             .ok_or_else(|| SlicerError::Internal("Invalid path encoding".to_string()))?;
         let c_path = CString::new(path_str)?;
 
-        let result = unsafe { ffi::slicer_load_model(self.ctx, c_path.as_ptr()) };
+        let result = unsafe { ffi::slicer_load_model(self.ctx.0, c_path.as_ptr()) };
 
         if result != SLICER_SUCCESS {
             let error_msg = self.get_error_message();
             return Err(SlicerError::from_code(result, error_msg));
         }
 
+        self.model_path = Some(path.to_path_buf());
         Ok(())
     }
 
@@ -172,7 +410,7 @@ impl Slicer {
 
         let result = unsafe {
             ffi::slicer_load_preset(
-                self.ctx,
+                self.ctx.0,
                 printer_c
                     .as_ref()
                     .map(|s| s.as_ptr())
@@ -193,6 +431,9 @@ impl Slicer {
             return Err(SlicerError::from_code(result, error_msg));
         }
 
+        self.printer_preset = config.printer_preset.clone();
+        self.filament_preset = config.filament_preset.clone();
+        self.process_preset = config.process_preset.clone();
         Ok(())
     }
 
@@ -202,7 +443,7 @@ impl Slicer {
         let c_value = CString::new(value)?;
 
         let result =
-            unsafe { ffi::slicer_set_config_param(self.ctx, c_key.as_ptr(), c_value.as_ptr()) };
+            unsafe { ffi::slicer_set_config_param(self.ctx.0, c_key.as_ptr(), c_value.as_ptr()) };
 
         if result != SLICER_SUCCESS {
             let error_msg = self.get_error_message();
@@ -212,20 +453,113 @@ impl Slicer {
         Ok(())
     }
 
-    /// Perform slicing and return statistics
+    /// Register a custom config-validation rule, checked alongside the
+    /// built-ins by [`validate_config`](Self::validate_config) (and, if
+    /// enabled, by [`slice`](Self::slice)).
+    pub fn add_config_rule(&mut self, rule: impl ConfigRule + Send + 'static) {
+        self.custom_rules.push(Box::new(rule));
+    }
+
+    /// If `fail` is true, [`slice`](Self::slice) first runs
+    /// [`validate_config`](Self::validate_config) and returns
+    /// [`SlicerError::ConfigValidation`] instead of slicing if any
+    /// `Error`-severity [`Diagnostic`] is present.
+    pub fn set_fail_on_lint_error(&mut self, fail: bool) {
+        self.fail_on_lint_error = fail;
+    }
+
+    /// Run the built-in and any custom [`ConfigRule`]s against the currently
+    /// resolved configuration (presets plus [`set_config_param`](Self::set_config_param)
+    /// overrides), without slicing.
+    pub fn validate_config(&self) -> Result<Vec<Diagnostic>> {
+        let config_json = self.get_config_json()?;
+        let value: serde_json::Value = serde_json::from_str(&config_json)
+            .map_err(|e| SlicerError::Internal(format!("Failed to parse config: {}", e)))?;
+
+        let mut params = std::collections::HashMap::new();
+        if let serde_json::Value::Object(map) = value {
+            for (key, v) in map {
+                let value_str = match v {
+                    serde_json::Value::String(s) => s,
+                    other => other.to_string(),
+                };
+                params.insert(key, value_str);
+            }
+        }
+
+        let ctx = ConfigContext::new(&params);
+        let mut diagnostics = Vec::new();
+        for rule in lint::default_rules() {
+            diagnostics.extend(rule.check(&ctx));
+        }
+        for rule in &self.custom_rules {
+            diagnostics.extend(rule.check(&ctx));
+        }
+
+        Ok(diagnostics)
+    }
+
+    /// Perform slicing and return a structured [`SliceOutcome`].
     ///
     /// This processes the model but doesn't export G-code yet.
     /// Call [`export_gcode`](Self::export_gcode) to write the G-code file.
-    pub fn slice(&mut self) -> Result<SlicerStats> {
-        let result = unsafe { ffi::slicer_process(self.ctx) };
+    ///
+    /// If [`set_fail_on_lint_error`](Self::set_fail_on_lint_error) was enabled,
+    /// this first runs [`validate_config`](Self::validate_config) and fails
+    /// fast on any `Error`-severity [`Diagnostic`].
+    pub fn slice(&mut self) -> Result<SliceOutcome> {
+        if self.fail_on_lint_error {
+            let diagnostics = self.validate_config()?;
+            if diagnostics.iter().any(|d| d.severity == Severity::Error) {
+                return Err(SlicerError::ConfigValidation(diagnostics));
+            }
+        }
+
+        let result = unsafe { ffi::slicer_process(self.ctx.0) };
+
+        // A panic caught in the progress trampoline couldn't be allowed to
+        // unwind across the FFI call above; re-raise it now that we're back
+        // on a pure-Rust stack.
+        if let Some(panic) = self.progress.as_mut().and_then(|s| s.panic.take()) {
+            std::panic::resume_unwind(panic);
+        }
+
+        if result == SLICER_CANCELLED {
+            return Ok(SliceOutcome {
+                stats: None,
+                status: SliceStatus::Cancelled,
+                warnings: Vec::new(),
+            });
+        }
 
         if result != SLICER_SUCCESS {
             let error_msg = self.get_error_message();
             return Err(SlicerError::from_code(result, error_msg));
         }
 
-        // Get statistics
-        let stats_ptr = unsafe { ffi::slicer_get_stats_json(self.ctx) };
+        let warnings = self.get_warnings()?;
+        let stats = self.get_stats()?;
+        let status = if warnings.is_empty() {
+            SliceStatus::Success
+        } else {
+            SliceStatus::PartialSuccess
+        };
+
+        Ok(SliceOutcome {
+            stats: Some(stats),
+            status,
+            warnings,
+        })
+    }
+
+    /// Fetch statistics for the most recently completed slice.
+    ///
+    /// Only meaningful after a [`slice`](Self::slice) call that returned
+    /// [`SliceStatus::Success`] or [`SliceStatus::PartialSuccess`] - that
+    /// outcome's `stats` field already carries the same data, so this is
+    /// mainly useful for call sites that only care about the final stats.
+    pub fn get_stats(&self) -> Result<SlicerStats> {
+        let stats_ptr = unsafe { ffi::slicer_get_stats_json(self.ctx.0) };
         if stats_ptr.is_null() {
             return Err(SlicerError::Internal(
                 "Failed to get statistics".to_string(),
@@ -240,6 +574,23 @@ impl Slicer {
             .map_err(|e| SlicerError::Internal(format!("Failed to parse statistics: {}", e)))
     }
 
+    /// Non-fatal adjustments the C++ core made while slicing (e.g.
+    /// auto-disabled supports, dropped thin walls, unfilled overhang
+    /// regions). Empty on a clean slice.
+    fn get_warnings(&self) -> Result<Vec<Diagnostic>> {
+        let ptr = unsafe { ffi::slicer_get_warnings_json(self.ctx.0) };
+        if ptr.is_null() {
+            return Ok(Vec::new());
+        }
+
+        let json = unsafe { CStr::from_ptr(ptr) }
+            .to_str()
+            .map_err(|_| SlicerError::Internal("Invalid UTF-8 in warnings".to_string()))?;
+
+        serde_json::from_str(json)
+            .map_err(|e| SlicerError::Internal(format!("Failed to parse warnings: {}", e)))
+    }
+
     /// Export G-code to file
     ///
     /// Must be called after [`slice`](Self::slice).
@@ -249,7 +600,73 @@ impl Slicer {
             .ok_or_else(|| SlicerError::Internal("Invalid path encoding".to_string()))?;
         let c_path = CString::new(path_str)?;
 
-        let result = unsafe { ffi::slicer_export_gcode(self.ctx, c_path.as_ptr()) };
+        let result = unsafe { ffi::slicer_export_gcode(self.ctx.0, c_path.as_ptr()) };
+
+        if result != SLICER_SUCCESS {
+            let error_msg = self.get_error_message();
+            return Err(SlicerError::from_code(result, error_msg));
+        }
+
+        Ok(())
+    }
+
+    /// Enumerate the printer/filament/process presets discoverable through the
+    /// C API, as a raw JSON string (one array per preset kind).
+    pub fn get_preset_info_json(&self) -> Result<String> {
+        let ptr = unsafe { ffi::slicer_get_preset_info_json(self.ctx.0) };
+        if ptr.is_null() {
+            return Err(SlicerError::Internal(
+                "Failed to get preset info".to_string(),
+            ));
+        }
+
+        unsafe { CStr::from_ptr(ptr) }
+            .to_str()
+            .map(|s| s.to_string())
+            .map_err(|_| SlicerError::Internal("Invalid UTF-8 in preset info".to_string()))
+    }
+
+    /// Dump the currently resolved configuration (presets plus any overrides
+    /// applied via [`set_config_param`](Self::set_config_param)) as a JSON object.
+    pub fn get_config_json(&self) -> Result<String> {
+        let ptr = unsafe { ffi::slicer_get_config_json(self.ctx.0) };
+        if ptr.is_null() {
+            return Err(SlicerError::Internal("Failed to get config".to_string()));
+        }
+
+        unsafe { CStr::from_ptr(ptr) }
+            .to_str()
+            .map(|s| s.to_string())
+            .map_err(|_| SlicerError::Internal("Invalid UTF-8 in config".to_string()))
+    }
+
+    /// Run cheap mesh-analysis passes on the loaded model without invoking
+    /// the full slicing pipeline, to drive UI and config decisions (or
+    /// reject oversized/non-manifold meshes) before committing to a slice.
+    pub fn inspect_model(&self) -> Result<ModelInfo> {
+        let info_ptr = unsafe { ffi::slicer_inspect_model_json(self.ctx.0) };
+        if info_ptr.is_null() {
+            return Err(SlicerError::Internal("Failed to inspect model".to_string()));
+        }
+
+        let info_json = unsafe { CStr::from_ptr(info_ptr) }
+            .to_str()
+            .map_err(|_| SlicerError::Internal("Invalid UTF-8 in model info".to_string()))?;
+
+        serde_json::from_str(info_json)
+            .map_err(|e| SlicerError::Internal(format!("Failed to parse model info: {}", e)))
+    }
+
+    /// Merge a JSON object of key/value overrides over the active preset.
+    ///
+    /// Unlike [`set_config_param`](Self::set_config_param), this applies many
+    /// parameters in one call; it's what [`slice_model`] uses to apply
+    /// `custom_config_json`, and what [`load_project`](Self::load_project)
+    /// uses to apply a bundle's resolved config.
+    pub fn set_config_json(&mut self, json: &str) -> Result<()> {
+        let c_json = CString::new(json)?;
+
+        let result = unsafe { ffi::slicer_set_config_json(self.ctx.0, c_json.as_ptr()) };
 
         if result != SLICER_SUCCESS {
             let error_msg = self.get_error_message();
@@ -259,9 +676,198 @@ impl Slicer {
         Ok(())
     }
 
+    /// Snapshot the currently resolved configuration to a [`SlicerProject`]
+    /// bundle and write it to `path`. The backend is chosen by extension:
+    /// `.json` writes pretty-printed JSON, anything else writes the compact
+    /// MessagePack encoding.
+    pub fn export_project(&self, path: &Path) -> Result<()> {
+        let config_json = self.get_config_json()?;
+        let config: std::collections::HashMap<String, String> = serde_json::from_str(&config_json)
+            .map_err(|e| SlicerError::Internal(format!("Failed to parse config: {}", e)))?;
+
+        let mut project = SlicerProject::new(config);
+        project.model_paths = self.model_path.iter().cloned().collect();
+        project.printer_preset = self.printer_preset.clone();
+        project.filament_preset = self.filament_preset.clone();
+        project.process_preset = self.process_preset.clone();
+
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            std::fs::write(path, project.to_json()?)?;
+        } else {
+            std::fs::write(path, project.to_bytes()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load a [`SlicerProject`] bundle previously written by
+    /// [`export_project`](Self::export_project): loads its first model path
+    /// (if any), applies its resolved config over the active preset via
+    /// [`set_config_json`](Self::set_config_json), and restores the preset
+    /// lineage bookkeeping used by a subsequent `export_project`. The
+    /// backend is chosen by extension, the same way as `export_project`.
+    pub fn load_project(&mut self, path: &Path) -> Result<()> {
+        let project = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            let contents = std::fs::read_to_string(path)?;
+            SlicerProject::from_json(&contents)?
+        } else {
+            let contents = std::fs::read(path)?;
+            SlicerProject::from_bytes(&contents)?
+        };
+
+        if let Some(model_path) = project.model_paths.first() {
+            self.load_model(model_path)?;
+        }
+
+        let config_json = serde_json::to_string(&project.config)
+            .map_err(|e| SlicerError::Internal(format!("Failed to encode config: {}", e)))?;
+        self.set_config_json(&config_json)?;
+
+        self.printer_preset = project.printer_preset;
+        self.filament_preset = project.filament_preset;
+        self.process_preset = project.process_preset;
+        Ok(())
+    }
+
+    /// Search `space` for the configuration that minimizes `objective`
+    /// (scored from the resulting [`SlicerStats`]), according to `strategy`,
+    /// against the currently loaded model. Repeated candidates - by resolved
+    /// parameter hash - are served from a cache instead of re-sliced. Stops
+    /// early once `budget` is exhausted.
+    pub fn sweep(
+        &mut self,
+        space: &SweepSpace,
+        objective: &Objective,
+        strategy: Strategy,
+        budget: SweepBudget,
+    ) -> Result<SweepResult> {
+        let mut cache: std::collections::HashMap<u64, Trial> = std::collections::HashMap::new();
+        let mut tracker = sweep::BudgetTracker::new(budget);
+        let mut trials = Vec::new();
+
+        match strategy {
+            Strategy::ExhaustiveGrid => {
+                for candidate in sweep::grid_candidates(space) {
+                    if tracker.exhausted() {
+                        break;
+                    }
+                    let trial = self.evaluate_candidate(&candidate, objective, &mut cache)?;
+                    trials.push(trial);
+                    tracker.record();
+                }
+            }
+            Strategy::HillClimb => {
+                let mut current: HashMap<String, String> = space
+                    .params()
+                    .iter()
+                    .filter_map(|(key, values)| values.first().map(|v| (key.clone(), v.clone())))
+                    .collect();
+
+                let mut current_trial = self.evaluate_candidate(&current, objective, &mut cache)?;
+                trials.push(current_trial.clone());
+                tracker.record();
+
+                let mut improved = true;
+                while improved && !tracker.exhausted() {
+                    improved = false;
+
+                    for (key, values) in space.params() {
+                        if tracker.exhausted() {
+                            break;
+                        }
+
+                        let mut best_for_param: Option<Trial> = None;
+                        for value in values {
+                            if tracker.exhausted() {
+                                break;
+                            }
+
+                            let mut candidate = current.clone();
+                            candidate.insert(key.clone(), value.clone());
+                            let trial =
+                                self.evaluate_candidate(&candidate, objective, &mut cache)?;
+                            trials.push(trial.clone());
+                            tracker.record();
+
+                            if best_for_param
+                                .as_ref()
+                                .map_or(true, |b| trial.score < b.score)
+                            {
+                                best_for_param = Some(trial);
+                            }
+                        }
+
+                        if let Some(best) = best_for_param {
+                            if best.score < current_trial.score {
+                                current = best.config.clone();
+                                current_trial = best;
+                                improved = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let best = trials
+            .iter()
+            .filter(|t| t.stats.is_some())
+            .min_by(|a, b| {
+                a.score
+                    .partial_cmp(&b.score)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned();
+
+        Ok(SweepResult { best, trials })
+    }
+
+    /// Apply one candidate's parameters and slice, or return the cached
+    /// trial if this exact combination (by resolved config hash) already ran.
+    fn evaluate_candidate(
+        &mut self,
+        config: &HashMap<String, String>,
+        objective: &Objective,
+        cache: &mut std::collections::HashMap<u64, Trial>,
+    ) -> Result<Trial> {
+        let hash = sweep::config_hash(config);
+        if let Some(cached) = cache.get(&hash) {
+            return Ok(cached.clone());
+        }
+
+        for (key, value) in config {
+            self.set_config_param(key, value)?;
+        }
+
+        // A candidate's parameters can legitimately make slicing fail (e.g.
+        // an extreme `layer_height`); that's a bad trial, not a bad sweep,
+        // so it's recorded with an infinite score instead of aborting the
+        // whole sweep and losing every trial collected so far.
+        let (stats, score) = match self.slice() {
+            Ok(outcome) => {
+                let score = outcome
+                    .stats
+                    .as_ref()
+                    .map(objective)
+                    .unwrap_or(f64::INFINITY);
+                (outcome.stats, score)
+            }
+            Err(_) => (None, f64::INFINITY),
+        };
+
+        let trial = Trial {
+            config: config.clone(),
+            stats,
+            score,
+        };
+
+        cache.insert(hash, trial.clone());
+        Ok(trial)
+    }
+
     /// Get the last error message from the C API
     fn get_error_message(&self) -> Option<String> {
-        let error_ptr = unsafe { ffi::slicer_get_last_error(self.ctx) };
+        let error_ptr = unsafe { ffi::slicer_get_last_error(self.ctx.0) };
         if error_ptr.is_null() {
             return None;
         }
@@ -273,16 +879,49 @@ impl Slicer {
     }
 }
 
-impl Drop for Slicer {
-    fn drop(&mut self) {
-        unsafe { ffi::slicer_destroy(self.ctx) };
-    }
-}
+// Slicer's Drop comes for free from its `ctx: Arc<generated::ContextHandle>`
+// field, which releases the underlying `SlicerContext*` via
+// `ffi::slicer_destroy` once the last reference (this `Slicer`, plus any
+// `CancelHandle`s obtained from it) is dropped.
 
-// Safety: SlicerContext is designed to be used from a single thread
+// Safety: SlicerContext is designed to be used from a single thread at a
+// time, not from multiple threads concurrently - moving it to another
+// thread and continuing to use it there exclusively is fine. This also
+// requires `ProgressState.callback` to be `Send` (enforced by
+// `set_progress_callback`'s bound), since a registered callback moves with
+// the `Slicer` and must still be safe to invoke from whichever thread ends
+// up calling it. Custom config rules move with it too, so they are likewise
+// required to be `Send` by `add_config_rule`.
 // The underlying C++ objects are not thread-safe
 unsafe impl Send for Slicer {}
 
+/// A cloneable, thread-safe handle that can request cancellation of a
+/// [`Slicer`]'s in-flight [`slice`](Slicer::slice) call from a different
+/// thread than the one running it. Obtained via
+/// [`Slicer::cancel_handle`].
+///
+/// Unlike `Slicer` itself (`Send` but not `Sync`), this type is safe to
+/// share across threads: it keeps the underlying context alive via a shared
+/// `Arc` (even if the owning `Slicer` is dropped first) and only ever calls
+/// `ffi::slicer_cancel`, which the C++ core is designed to handle while
+/// `slicer_process` is concurrently running on another thread.
+#[derive(Clone)]
+pub struct CancelHandle {
+    ctx: Arc<generated::ContextHandle>,
+}
+
+unsafe impl Send for CancelHandle {}
+unsafe impl Sync for CancelHandle {}
+
+impl CancelHandle {
+    /// Request that the `Slicer` this handle was obtained from abort its
+    /// in-flight `slice()` call as soon as the C++ core next checks for
+    /// cancellation.
+    pub fn cancel(&self) {
+        unsafe { ffi::slicer_cancel(self.ctx.0) };
+    }
+}
+
 /// Slice a model in one function call (Simple API)
 ///
 /// This is a convenience function that creates a slicer, loads the model,
@@ -327,11 +966,18 @@ pub fn slice_model(
     }
 
     if let Some(ref json) = config.custom_config_json {
-        // TODO: Apply JSON config (not yet implemented in C API)
-        let _ = json;
+        slicer.set_config_json(json)?;
     }
 
-    let stats = slicer.slice()?;
+    let outcome = slicer.slice()?;
+
+    let Some(stats) = outcome.stats else {
+        return Err(SlicerError::Internal(format!(
+            "Slicing did not produce statistics (status: {:?})",
+            outcome.status
+        )));
+    };
+
     slicer.export_gcode(output_path)?;
 
     Ok(stats)
@@ -0,0 +1,387 @@
+//! Config validation/lint rules run before slicing.
+//!
+//! A bad parameter combination (e.g. a layer height larger than the nozzle
+//! diameter) otherwise only surfaces as a late [`crate::SlicerError::ProcessFailed`]
+//! or silently produces garbage. [`ConfigRule`]s inspect the resolved config
+//! and report [`Diagnostic`]s with a [`Severity`], the same way a lint engine
+//! maps rule output to severities instead of hard-coding each error type.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// How serious a [`Diagnostic`] is. Ordered so `Error > Warning > Info`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// A single finding from a [`ConfigRule`], or a non-fatal adjustment the
+/// C++ core reported while slicing (see [`crate::SliceOutcome::warnings`]).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Short, stable identifier for this kind of finding (e.g. `"layer-height-vs-nozzle"`).
+    pub code: &'static str,
+    pub message: String,
+    /// Config keys this diagnostic is about, for UIs that want to highlight them.
+    pub keys: Vec<String>,
+}
+
+impl Diagnostic {
+    fn new(
+        severity: Severity,
+        code: &'static str,
+        message: impl Into<String>,
+        keys: &[&str],
+    ) -> Self {
+        Diagnostic {
+            severity,
+            code,
+            message: message.into(),
+            keys: keys.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Resolved key/value config plus printer metadata a [`ConfigRule`] inspects.
+pub struct ConfigContext<'a> {
+    params: &'a HashMap<String, String>,
+}
+
+impl<'a> ConfigContext<'a> {
+    pub fn new(params: &'a HashMap<String, String>) -> Self {
+        ConfigContext { params }
+    }
+
+    /// Raw string value of a resolved config key, if set.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(|s| s.as_str())
+    }
+
+    /// Numeric value of a config key, stripping a trailing `%` if present
+    /// (BambuStudio stores some parameters, like infill density, as percentages).
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get(key)?.trim_end_matches('%').trim().parse().ok()
+    }
+}
+
+/// A single config-validation rule. Built-ins are listed in [`default_rules`];
+/// callers can implement this trait for project-specific checks and register
+/// them via [`crate::Slicer::add_config_rule`], after which
+/// [`crate::Slicer::validate_config`] checks them alongside the defaults.
+pub trait ConfigRule {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic>;
+}
+
+/// Layer height should not exceed the nozzle diameter; above ~75% of it,
+/// print quality degrades noticeably before it becomes outright unprintable.
+struct LayerHeightVsNozzle;
+
+impl ConfigRule for LayerHeightVsNozzle {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic> {
+        let (Some(layer_height), Some(nozzle_diameter)) =
+            (ctx.get_f64("layer_height"), ctx.get_f64("nozzle_diameter"))
+        else {
+            return vec![];
+        };
+
+        if layer_height > nozzle_diameter {
+            vec![Diagnostic::new(
+                Severity::Error,
+                "layer-height-vs-nozzle",
+                format!(
+                    "layer_height ({layer_height}mm) exceeds nozzle_diameter ({nozzle_diameter}mm)"
+                ),
+                &["layer_height", "nozzle_diameter"],
+            )]
+        } else if layer_height > nozzle_diameter * 0.75 {
+            vec![Diagnostic::new(
+                Severity::Warning,
+                "layer-height-vs-nozzle",
+                format!(
+                    "layer_height ({layer_height}mm) is over 75% of nozzle_diameter ({nozzle_diameter}mm); expect rough surfaces"
+                ),
+                &["layer_height", "nozzle_diameter"],
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// First-layer height should stay within a small window around the nozzle
+/// diameter - too thin adheres poorly, too thick risks nozzle collisions.
+struct FirstLayerHeightBounds;
+
+impl ConfigRule for FirstLayerHeightBounds {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic> {
+        let Some(first_layer_height) = ctx.get_f64("initial_layer_print_height") else {
+            return vec![];
+        };
+
+        if first_layer_height < 0.05 {
+            vec![Diagnostic::new(
+                Severity::Warning,
+                "first-layer-height-bounds",
+                format!("initial_layer_print_height ({first_layer_height}mm) is unusually thin and may not adhere well"),
+                &["initial_layer_print_height"],
+            )]
+        } else if let Some(nozzle_diameter) = ctx.get_f64("nozzle_diameter") {
+            if first_layer_height > nozzle_diameter {
+                vec![Diagnostic::new(
+                    Severity::Error,
+                    "first-layer-height-bounds",
+                    format!("initial_layer_print_height ({first_layer_height}mm) exceeds nozzle_diameter ({nozzle_diameter}mm)"),
+                    &["initial_layer_print_height", "nozzle_diameter"],
+                )]
+            } else {
+                vec![]
+            }
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Sparse infill density must be a sane percentage.
+struct InfillDensityRange;
+
+impl ConfigRule for InfillDensityRange {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic> {
+        let Some(density) = ctx.get_f64("sparse_infill_density") else {
+            return vec![];
+        };
+
+        if !(0.0..=100.0).contains(&density) {
+            vec![Diagnostic::new(
+                Severity::Error,
+                "infill-density-range",
+                format!("sparse_infill_density ({density}%) must be between 0 and 100"),
+                &["sparse_infill_density"],
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// Outer wall speed shouldn't exceed the overall print speed - it's usually
+/// meant to be slower, for better surface quality.
+struct SpeedVsAccelerationSanity;
+
+impl ConfigRule for SpeedVsAccelerationSanity {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic> {
+        let (Some(outer_wall_speed), Some(print_speed)) =
+            (ctx.get_f64("outer_wall_speed"), ctx.get_f64("print_speed"))
+        else {
+            return vec![];
+        };
+
+        if outer_wall_speed > print_speed {
+            vec![Diagnostic::new(
+                Severity::Warning,
+                "speed-vs-acceleration-sanity",
+                format!(
+                    "outer_wall_speed ({outer_wall_speed}mm/s) is faster than print_speed ({print_speed}mm/s)"
+                ),
+                &["outer_wall_speed", "print_speed"],
+            )]
+        } else {
+            vec![]
+        }
+    }
+}
+
+/// `wall_loops=0` prints a shell-less model, which is rarely intentional.
+struct WallLoopsZero;
+
+impl ConfigRule for WallLoopsZero {
+    fn check(&self, ctx: &ConfigContext) -> Vec<Diagnostic> {
+        match ctx.get_f64("wall_loops") {
+            Some(loops) if loops == 0.0 => vec![Diagnostic::new(
+                Severity::Warning,
+                "wall-loops-zero",
+                "wall_loops is 0; the model will print with no perimeter shell",
+                &["wall_loops"],
+            )],
+            _ => vec![],
+        }
+    }
+}
+
+/// The built-in rule set, checked by [`crate::Slicer::validate_config`] in
+/// addition to any rules pushed via [`crate::Slicer::add_config_rule`].
+pub fn default_rules() -> Vec<Box<dyn ConfigRule>> {
+    vec![
+        Box::new(LayerHeightVsNozzle),
+        Box::new(FirstLayerHeightBounds),
+        Box::new(InfillDensityRange),
+        Box::new(SpeedVsAccelerationSanity),
+        Box::new(WallLoopsZero),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn get_f64_strips_trailing_percent() {
+        let params = context(&[("sparse_infill_density", "25%")]);
+        let ctx = ConfigContext::new(&params);
+        assert_eq!(ctx.get_f64("sparse_infill_density"), Some(25.0));
+    }
+
+    #[test]
+    fn get_f64_parses_plain_numbers() {
+        let params = context(&[("layer_height", "0.2")]);
+        let ctx = ConfigContext::new(&params);
+        assert_eq!(ctx.get_f64("layer_height"), Some(0.2));
+    }
+
+    #[test]
+    fn get_f64_is_none_for_missing_or_unparseable() {
+        let params = context(&[("layer_height", "not-a-number")]);
+        let ctx = ConfigContext::new(&params);
+        assert_eq!(ctx.get_f64("layer_height"), None);
+        assert_eq!(ctx.get_f64("missing"), None);
+    }
+
+    #[test]
+    fn layer_height_vs_nozzle_silent_below_threshold() {
+        let params = context(&[("layer_height", "0.2"), ("nozzle_diameter", "0.4")]);
+        let ctx = ConfigContext::new(&params);
+        assert!(LayerHeightVsNozzle.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn layer_height_vs_nozzle_silent_at_exactly_75_percent() {
+        // 0.3 is exactly 75% of 0.4; the rule only warns once it's exceeded.
+        let params = context(&[("layer_height", "0.3"), ("nozzle_diameter", "0.4")]);
+        let ctx = ConfigContext::new(&params);
+        assert!(LayerHeightVsNozzle.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn layer_height_vs_nozzle_warns_just_over_75_percent() {
+        let params = context(&[("layer_height", "0.31"), ("nozzle_diameter", "0.4")]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = LayerHeightVsNozzle.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn layer_height_vs_nozzle_errors_when_exceeding_nozzle() {
+        let params = context(&[("layer_height", "0.5"), ("nozzle_diameter", "0.4")]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = LayerHeightVsNozzle.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn layer_height_vs_nozzle_silent_when_keys_missing() {
+        let params = context(&[("layer_height", "0.5")]);
+        let ctx = ConfigContext::new(&params);
+        assert!(LayerHeightVsNozzle.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn first_layer_height_bounds_warns_when_too_thin() {
+        let params = context(&[("initial_layer_print_height", "0.04")]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = FirstLayerHeightBounds.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn first_layer_height_bounds_errors_when_exceeding_nozzle() {
+        let params = context(&[
+            ("initial_layer_print_height", "0.5"),
+            ("nozzle_diameter", "0.4"),
+        ]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = FirstLayerHeightBounds.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn first_layer_height_bounds_silent_within_range() {
+        let params = context(&[
+            ("initial_layer_print_height", "0.2"),
+            ("nozzle_diameter", "0.4"),
+        ]);
+        let ctx = ConfigContext::new(&params);
+        assert!(FirstLayerHeightBounds.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn infill_density_range_accepts_boundary_values() {
+        for value in ["0", "100"] {
+            let params = context(&[("sparse_infill_density", value)]);
+            let ctx = ConfigContext::new(&params);
+            assert!(InfillDensityRange.check(&ctx).is_empty());
+        }
+    }
+
+    #[test]
+    fn infill_density_range_rejects_out_of_range_values() {
+        for value in ["-1", "101"] {
+            let params = context(&[("sparse_infill_density", value)]);
+            let ctx = ConfigContext::new(&params);
+            let diagnostics = InfillDensityRange.check(&ctx);
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].severity, Severity::Error);
+        }
+    }
+
+    #[test]
+    fn speed_vs_acceleration_sanity_warns_when_wall_faster_than_print() {
+        let params = context(&[("outer_wall_speed", "150"), ("print_speed", "100")]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = SpeedVsAccelerationSanity.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn speed_vs_acceleration_sanity_silent_when_equal_or_slower() {
+        let params = context(&[("outer_wall_speed", "100"), ("print_speed", "100")]);
+        let ctx = ConfigContext::new(&params);
+        assert!(SpeedVsAccelerationSanity.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn wall_loops_zero_warns_at_exactly_zero() {
+        let params = context(&[("wall_loops", "0")]);
+        let ctx = ConfigContext::new(&params);
+        let diagnostics = WallLoopsZero.check(&ctx);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn wall_loops_zero_silent_above_zero() {
+        let params = context(&[("wall_loops", "2")]);
+        let ctx = ConfigContext::new(&params);
+        assert!(WallLoopsZero.check(&ctx).is_empty());
+    }
+
+    #[test]
+    fn default_rules_contains_all_built_ins() {
+        assert_eq!(default_rules().len(), 5);
+    }
+}
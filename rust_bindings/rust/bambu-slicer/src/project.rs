@@ -0,0 +1,132 @@
+//! Serializable project/config bundles.
+//!
+//! A [`SlicerProject`] snapshots everything needed to reproduce a slice -
+//! model path(s), the fully-resolved config map, and preset lineage - so it
+//! can be written to disk and reloaded later instead of re-resolving presets
+//! and overrides from scratch. Two backends are supported: JSON, for bundles
+//! a human might want to open and edit, and a compact MessagePack encoding
+//! (via `rmp-serde`) for fast reload and caching.
+
+use crate::{Result, SlicerError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// On-disk schema version. Bump this whenever [`SlicerProject`]'s shape
+/// changes in a way that would silently corrupt round-tripping; loaders
+/// reject bundles tagged with a version they don't recognize.
+const PROJECT_SCHEMA_VERSION: u32 = 1;
+
+/// A saved snapshot of a slicer configuration: model path(s), the
+/// fully-resolved config key/value map, and the preset names it was
+/// resolved from (if any).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlicerProject {
+    schema_version: u32,
+
+    /// Model file(s) this project slices.
+    pub model_paths: Vec<PathBuf>,
+
+    /// Fully-resolved config key/value pairs (presets + overrides flattened).
+    pub config: HashMap<String, String>,
+
+    pub printer_preset: Option<String>,
+    pub filament_preset: Option<String>,
+    pub process_preset: Option<String>,
+}
+
+impl SlicerProject {
+    /// Build a new project bundle from a fully-resolved config map.
+    pub fn new(config: HashMap<String, String>) -> Self {
+        SlicerProject {
+            schema_version: PROJECT_SCHEMA_VERSION,
+            model_paths: Vec::new(),
+            config,
+            printer_preset: None,
+            filament_preset: None,
+            process_preset: None,
+        }
+    }
+
+    /// Serialize as human-readable, pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| SlicerError::Internal(format!("Failed to serialize project: {}", e)))
+    }
+
+    /// Parse a JSON bundle previously produced by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self> {
+        let project: Self = serde_json::from_str(json)
+            .map_err(|e| SlicerError::ConfigParse(format!("Invalid project JSON: {}", e)))?;
+        project.check_schema_version()?;
+        Ok(project)
+    }
+
+    /// Serialize as a compact MessagePack bundle, for fast reload or caching.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        rmp_serde::to_vec(self)
+            .map_err(|e| SlicerError::Internal(format!("Failed to encode project: {}", e)))
+    }
+
+    /// Parse a binary bundle previously produced by [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let project: Self = rmp_serde::from_slice(bytes)
+            .map_err(|e| SlicerError::ConfigParse(format!("Invalid project bundle: {}", e)))?;
+        project.check_schema_version()?;
+        Ok(project)
+    }
+
+    fn check_schema_version(&self) -> Result<()> {
+        if self.schema_version != PROJECT_SCHEMA_VERSION {
+            return Err(SlicerError::ConfigParse(format!(
+                "Unsupported project schema version {} (expected {})",
+                self.schema_version, PROJECT_SCHEMA_VERSION
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SlicerProject {
+        let mut config = HashMap::new();
+        config.insert("layer_height".to_string(), "0.2".to_string());
+        config.insert("nozzle_diameter".to_string(), "0.4".to_string());
+
+        let mut project = SlicerProject::new(config);
+        project.model_paths.push(PathBuf::from("model.3mf"));
+        project.printer_preset = Some("Bambu Lab A1".to_string());
+        project
+    }
+
+    #[test]
+    fn json_round_trip_reproduces_config() {
+        let project = sample();
+        let json = project.to_json().expect("serialize");
+        let reloaded = SlicerProject::from_json(&json).expect("deserialize");
+        assert_eq!(reloaded.config, project.config);
+        assert_eq!(reloaded.model_paths, project.model_paths);
+        assert_eq!(reloaded.printer_preset, project.printer_preset);
+    }
+
+    #[test]
+    fn binary_round_trip_reproduces_config() {
+        let project = sample();
+        let bytes = project.to_bytes().expect("encode");
+        let reloaded = SlicerProject::from_bytes(&bytes).expect("decode");
+        assert_eq!(reloaded.config, project.config);
+    }
+
+    #[test]
+    fn rejects_unknown_schema_version() {
+        let mut project = sample();
+        project.schema_version = PROJECT_SCHEMA_VERSION + 1;
+        let json = project.to_json().expect("serialize");
+
+        let err = SlicerProject::from_json(&json).expect_err("should reject newer schema");
+        assert!(matches!(err, SlicerError::ConfigParse(_)));
+    }
+}
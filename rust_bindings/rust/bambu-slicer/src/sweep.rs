@@ -0,0 +1,181 @@
+//! Parameter auto-tuning / sweep subsystem.
+//!
+//! Searches slicing parameters to meet a user objective - minimize print
+//! time, minimize filament, hit a target strength proxy - subject to a
+//! [`SweepSpace`]. Borrows the observer/feedback-loop structure of modular
+//! fuzzers (run a candidate, score it, decide what to try next) but applies
+//! it to slicing-parameter search instead of input mutation. The driver
+//! lives on [`crate::Slicer::sweep`], since it needs to re-run
+//! `set_config_param` + `slice()` against a loaded model for each candidate.
+
+use crate::SlicerStats;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, Instant};
+
+/// Parameters to vary and the candidate values to try for each, in the
+/// order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct SweepSpace {
+    pub(crate) params: Vec<(String, Vec<String>)>,
+}
+
+impl SweepSpace {
+    pub fn new() -> Self {
+        SweepSpace::default()
+    }
+
+    /// Add a parameter to vary, with the candidate values to try (in order).
+    pub fn param(
+        mut self,
+        key: impl Into<String>,
+        values: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.params
+            .push((key.into(), values.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    pub fn params(&self) -> &[(String, Vec<String>)] {
+        &self.params
+    }
+}
+
+/// Maps a [`SlicerStats`] to a scalar score; **lower is better**. Negate a
+/// quantity to maximize it instead (e.g. `-stats.total_weight` to prefer a
+/// heavier part), or compute a distance-to-target for a strength proxy.
+pub type Objective = Box<dyn Fn(&SlicerStats) -> f64>;
+
+/// Search strategy over a [`SweepSpace`].
+pub enum Strategy {
+    /// Try every combination of candidate values.
+    ExhaustiveGrid,
+    /// Start from each parameter's first candidate value, then repeatedly
+    /// vary one parameter at a time, keeping a change only if it improves
+    /// the score. Stops once a full pass over every parameter makes no
+    /// improvement.
+    HillClimb,
+}
+
+/// Early-stop conditions for a sweep. Leave a field `None` to not bound it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SweepBudget {
+    pub max_trials: Option<usize>,
+    pub max_wall_time: Option<Duration>,
+}
+
+/// One evaluated candidate configuration.
+#[derive(Debug, Clone)]
+pub struct Trial {
+    pub config: HashMap<String, String>,
+    /// `None` if the candidate was cancelled or otherwise produced no stats;
+    /// such trials score `f64::INFINITY` so they never win.
+    pub stats: Option<SlicerStats>,
+    pub score: f64,
+}
+
+/// Result of a sweep: the best trial found (if any completed) plus the full
+/// trial log, in evaluation order.
+#[derive(Debug, Clone, Default)]
+pub struct SweepResult {
+    pub best: Option<Trial>,
+    pub trials: Vec<Trial>,
+}
+
+/// Tracks the early-stop conditions of a [`SweepBudget`] across trials.
+pub(crate) struct BudgetTracker {
+    budget: SweepBudget,
+    started: Instant,
+    trial_count: usize,
+}
+
+impl BudgetTracker {
+    pub(crate) fn new(budget: SweepBudget) -> Self {
+        BudgetTracker {
+            budget,
+            started: Instant::now(),
+            trial_count: 0,
+        }
+    }
+
+    pub(crate) fn exhausted(&self) -> bool {
+        if let Some(max) = self.budget.max_trials {
+            if self.trial_count >= max {
+                return true;
+            }
+        }
+        if let Some(max_time) = self.budget.max_wall_time {
+            if self.started.elapsed() >= max_time {
+                return true;
+            }
+        }
+        false
+    }
+
+    pub(crate) fn record(&mut self) {
+        self.trial_count += 1;
+    }
+}
+
+/// Hash a resolved candidate config, independent of key insertion order, so
+/// the same combination of swept parameters always maps to the same cache key.
+pub(crate) fn config_hash(config: &HashMap<String, String>) -> u64 {
+    let mut entries: Vec<_> = config.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut hasher = DefaultHasher::new();
+    for (key, value) in entries {
+        key.hash(&mut hasher);
+        value.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Every combination of `space`'s candidate values (the full grid).
+pub(crate) fn grid_candidates(space: &SweepSpace) -> Vec<HashMap<String, String>> {
+    let mut candidates = vec![HashMap::new()];
+    for (key, values) in &space.params {
+        let mut next = Vec::with_capacity(candidates.len() * values.len().max(1));
+        for candidate in &candidates {
+            for value in values {
+                let mut extended = candidate.clone();
+                extended.insert(key.clone(), value.clone());
+                next.push(extended);
+            }
+        }
+        candidates = next;
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_candidates_is_cartesian_product() {
+        let space = SweepSpace::new()
+            .param("layer_height", ["0.12", "0.20"])
+            .param("wall_loops", ["2", "3"]);
+
+        let candidates = grid_candidates(&space);
+        assert_eq!(candidates.len(), 4);
+        assert!(candidates
+            .iter()
+            .any(|c| c["layer_height"] == "0.12" && c["wall_loops"] == "3"));
+    }
+
+    #[test]
+    fn config_hash_ignores_insertion_order() {
+        let mut a = HashMap::new();
+        a.insert("layer_height".to_string(), "0.2".to_string());
+        a.insert("wall_loops".to_string(), "3".to_string());
+
+        let mut b = HashMap::new();
+        b.insert("wall_loops".to_string(), "3".to_string());
+        b.insert("layer_height".to_string(), "0.2".to_string());
+
+        assert_eq!(config_hash(&a), config_hash(&b));
+    }
+}
@@ -1,12 +1,22 @@
 use std::path::{Path, PathBuf};
-
-use aws_sdk_s3::{primitives::ByteStream, Client as S3Client};
+use std::sync::Arc;
+
+use aws_sdk_s3::{
+    config::{Builder as S3ConfigBuilder, Region},
+    primitives::ByteStream,
+    types::{CompletedMultipartUpload, CompletedPart},
+    Client as S3Client,
+};
 use bambu_slicer::{Slicer, SlicerConfig, SlicerStats};
 use lambda_runtime::{tracing, Error, LambdaEvent};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tempfile::TempDir;
-use tokio::{fs, io::AsyncReadExt, sync::OnceCell};
+use tokio::{
+    fs,
+    io::AsyncReadExt,
+    sync::{OnceCell, Semaphore},
+};
 
 #[derive(Debug, Deserialize)]
 struct SliceRequest {
@@ -48,13 +58,51 @@ struct SliceOutcome {
     config: Value,
 }
 
+/// Outcome of a single item in a batch (array-form) invocation.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchItemStatus {
+    Success,
+    Error,
+}
+
+/// Per-item result of a batch invocation. A failing item reports its error
+/// here rather than aborting the rest of the batch.
+#[derive(Debug, Serialize)]
+struct BatchItemResult {
+    index: usize,
+    status: BatchItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<SlicerStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
 static S3_CLIENT: OnceCell<S3Client> = OnceCell::const_new();
 
+/// Builds the S3 client from the environment. Honors `S3_ENDPOINT_URL`,
+/// `S3_REGION`, and `S3_FORCE_PATH_STYLE` so self-hosted S3-compatible
+/// backends (MinIO, Garage, ...) work without code changes in
+/// `parse_s3_location`/`download_input`/`upload_output`. Falls back to the
+/// plain AWS env-based config when no endpoint override is set.
 async fn s3_client() -> &'static S3Client {
     S3_CLIENT
         .get_or_init(|| async {
-            let config = aws_config::load_from_env().await;
-            S3Client::new(&config)
+            let shared_config = aws_config::load_from_env().await;
+            let mut builder = S3ConfigBuilder::from(&shared_config);
+
+            if let Ok(endpoint_url) = std::env::var("S3_ENDPOINT_URL") {
+                builder = builder.endpoint_url(endpoint_url);
+            }
+            if let Ok(region) = std::env::var("S3_REGION") {
+                builder = builder.region(Region::new(region));
+            }
+            if let Ok(force_path_style) = std::env::var("S3_FORCE_PATH_STYLE") {
+                builder =
+                    builder.force_path_style(force_path_style == "true" || force_path_style == "1");
+            }
+
+            S3Client::from_conf(builder.build())
         })
         .await
 }
@@ -113,26 +161,187 @@ async fn download_input(
     Ok(local_path)
 }
 
+/// Files larger than this go through [`upload_output_multipart`] instead of
+/// a single `put_object`, to keep memory bounded regardless of output size.
+const MULTIPART_THRESHOLD_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Size of each part streamed by [`upload_output_multipart`] - the common S3
+/// chunk size, and comfortably above the 5 MiB minimum S3 enforces for every
+/// part but the last.
+const MULTIPART_PART_SIZE_BYTES: usize = 8 * 1024 * 1024;
+
 async fn upload_output(
     client: &S3Client,
     bucket: &str,
     key: &str,
     path: &Path,
 ) -> Result<(), Error> {
-    tracing::info!("Uploading {:?} to s3://{}/{}", path, bucket, key);
-    let bytes = fs::read(path).await?;
-    let body = ByteStream::from(bytes);
+    let size = fs::metadata(path).await?.len();
+
+    if size > MULTIPART_THRESHOLD_BYTES {
+        upload_output_multipart(client, bucket, key, path).await
+    } else {
+        tracing::info!("Uploading {:?} to s3://{}/{}", path, bucket, key);
+        let bytes = fs::read(path).await?;
+        let body = ByteStream::from(bytes);
+
+        client
+            .put_object()
+            .bucket(bucket)
+            .key(key)
+            .body(body)
+            .send()
+            .await?;
+        Ok(())
+    }
+}
 
-    client
-        .put_object()
+/// Stream `path` to S3 in fixed-size parts instead of buffering the whole
+/// file, so memory stays bounded for large multi-plate slices. Aborts the
+/// upload on any error to avoid leaking incomplete parts and their charges.
+async fn upload_output_multipart(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    path: &Path,
+) -> Result<(), Error> {
+    tracing::info!(
+        "Uploading {:?} to s3://{}/{} via multipart upload",
+        path,
+        bucket,
+        key
+    );
+
+    let create = client
+        .create_multipart_upload()
         .bucket(bucket)
         .key(key)
-        .body(body)
         .send()
         .await?;
+    let upload_id = create
+        .upload_id()
+        .ok_or_else(|| "create_multipart_upload did not return an upload_id".to_string())?
+        .to_string();
+
+    let result = async {
+        let parts = upload_parts(client, bucket, key, &upload_id, path).await?;
+        let completed_upload = CompletedMultipartUpload::builder()
+            .set_parts(Some(parts))
+            .build();
+
+        client
+            .complete_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .multipart_upload(completed_upload)
+            .send()
+            .await?;
+        Ok::<(), Error>(())
+    }
+    .await;
+
+    if let Err(e) = result {
+        tracing::error!("Multipart upload of {:?} failed, aborting: {}", path, e);
+        let _ = client
+            .abort_multipart_upload()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(&upload_id)
+            .send()
+            .await;
+        return Err(e);
+    }
     Ok(())
 }
 
+/// Read `path` in `MULTIPART_PART_SIZE_BYTES` chunks, uploading each as a
+/// part. Every part but the last is exactly `MULTIPART_PART_SIZE_BYTES`
+/// (S3 requires all but the final part to be at least 5 MiB).
+async fn upload_parts(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+    path: &Path,
+) -> Result<Vec<CompletedPart>, Error> {
+    let mut file = fs::File::open(path).await?;
+    let mut buf = vec![0u8; MULTIPART_PART_SIZE_BYTES];
+    let mut filled = 0usize;
+    let mut part_number = 1i32;
+    let mut parts = Vec::new();
+
+    loop {
+        let read = file.read(&mut buf[filled..]).await?;
+        if read == 0 {
+            break;
+        }
+        filled += read;
+
+        if filled == buf.len() {
+            parts.push(
+                upload_one_part(
+                    client,
+                    bucket,
+                    key,
+                    upload_id,
+                    part_number,
+                    buf[..filled].to_vec(),
+                )
+                .await?,
+            );
+            part_number += 1;
+            filled = 0;
+        }
+    }
+
+    if filled > 0 {
+        parts.push(
+            upload_one_part(
+                client,
+                bucket,
+                key,
+                upload_id,
+                part_number,
+                buf[..filled].to_vec(),
+            )
+            .await?,
+        );
+    }
+
+    parts.sort_by_key(|part| part.part_number());
+    Ok(parts)
+}
+
+async fn upload_one_part(
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    upload_id: &str,
+    part_number: i32,
+    chunk: Vec<u8>,
+) -> Result<CompletedPart, Error> {
+    let response = client
+        .upload_part()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .part_number(part_number)
+        .body(ByteStream::from(chunk))
+        .send()
+        .await?;
+
+    let e_tag = response
+        .e_tag()
+        .ok_or_else(|| "upload_part response missing e_tag".to_string())?
+        .to_string();
+
+    Ok(CompletedPart::builder()
+        .part_number(part_number)
+        .e_tag(e_tag)
+        .build())
+}
+
 fn slice_with_presets(
     model_path: &Path,
     output_path: &Path,
@@ -150,7 +359,14 @@ fn slice_with_presets(
     };
     slicer.load_preset(&slicer_config)?;
 
-    slicer.slice()?;
+    let outcome = slicer.slice()?;
+    if outcome.stats.is_none() {
+        return Err(format!(
+            "Slicing did not produce statistics (status: {:?})",
+            outcome.status
+        )
+        .into());
+    }
     slicer.export_gcode(output_path)?;
 
     let stats = slicer.get_stats()?;
@@ -192,7 +408,14 @@ fn slice_with_custom_params(
         }
     }
 
-    slicer.slice()?;
+    let outcome = slicer.slice()?;
+    if outcome.stats.is_none() {
+        return Err(format!(
+            "Slicing did not produce statistics (status: {:?})",
+            outcome.status
+        )
+        .into());
+    }
     slicer.export_gcode(output_path)?;
 
     let stats = slicer.get_stats()?;
@@ -250,15 +473,7 @@ async fn ensure_resources_in_tmp() -> Result<(), Error> {
     Ok(())
 }
 
-pub(crate) async fn function_handler(event: LambdaEvent<Value>) -> Result<Value, Error> {
-    let payload = event.payload;
-    tracing::info!("Payload: {:?}", payload);
-
-    ensure_resources_in_tmp().await?;
-
-    let job: JobPayload = serde_json::from_value(payload)?;
-    let client = s3_client().await;
-
+async fn process_job(client: &S3Client, job: JobPayload) -> Result<SliceResponse, Error> {
     let (input_bucket, input_key) = parse_s3_location(&job.input_path)?;
     let (output_bucket, output_prefix) = normalize_output_prefix(&job.output_path)?;
 
@@ -274,11 +489,18 @@ pub(crate) async fn function_handler(event: LambdaEvent<Value>) -> Result<Value,
         custom_params: None,
     });
 
-    let outcome = if config.custom_params.is_some() {
-        slice_with_custom_params(&input_path, &output_gcode_path, &config)?
-    } else {
-        slice_with_presets(&input_path, &output_gcode_path, &config)?
-    };
+    // The FFI slice call is blocking (and can take minutes), so it runs on a
+    // blocking-pool thread rather than tying up a tokio worker for its whole
+    // duration.
+    let outcome = tokio::task::spawn_blocking(move || {
+        if config.custom_params.is_some() {
+            slice_with_custom_params(&input_path, &output_gcode_path, &config)
+        } else {
+            slice_with_presets(&input_path, &output_gcode_path, &config)
+        }
+    })
+    .await
+    .map_err(|join_err| format!("slicing task panicked: {}", join_err))??;
 
     let response = SliceResponse {
         stats: outcome.stats,
@@ -295,6 +517,77 @@ pub(crate) async fn function_handler(event: LambdaEvent<Value>) -> Result<Value,
     upload_output(client, &output_bucket, &gcode_key, &output_gcode_path).await?;
     upload_output(client, &output_bucket, &metadata_key, &metadata_json_path).await?;
 
+    Ok(response)
+}
+
+/// Upper bound on slicer instances running concurrently for one batch
+/// (array-form) invocation, so a large batch can't exhaust `/tmp` or spawn
+/// unbounded slicer processes.
+const BATCH_CONCURRENCY_LIMIT: usize = 4;
+
+async fn process_batch(client: &'static S3Client, jobs: Vec<JobPayload>) -> Vec<BatchItemResult> {
+    let semaphore = Arc::new(Semaphore::new(BATCH_CONCURRENCY_LIMIT));
+    let mut tasks = Vec::with_capacity(jobs.len());
+
+    for (index, job) in jobs.into_iter().enumerate() {
+        let semaphore = semaphore.clone();
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("batch semaphore was closed");
+
+            match process_job(client, job).await {
+                Ok(response) => BatchItemResult {
+                    index,
+                    status: BatchItemStatus::Success,
+                    stats: Some(response.stats),
+                    error: None,
+                },
+                Err(e) => BatchItemResult {
+                    index,
+                    status: BatchItemStatus::Error,
+                    stats: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (index, task) in tasks.into_iter().enumerate() {
+        match task.await {
+            Ok(result) => results.push(result),
+            Err(join_err) => results.push(BatchItemResult {
+                index,
+                status: BatchItemStatus::Error,
+                stats: None,
+                error: Some(format!("batch item task panicked: {}", join_err)),
+            }),
+        }
+    }
+    results.sort_by_key(|r| r.index);
+    results
+}
+
+pub(crate) async fn function_handler(event: LambdaEvent<Value>) -> Result<Value, Error> {
+    let payload = event.payload;
+    tracing::info!("Payload: {:?}", payload);
+
+    ensure_resources_in_tmp().await?;
+    let client = s3_client().await;
+
+    if let Value::Array(items) = payload {
+        let jobs = items
+            .into_iter()
+            .map(serde_json::from_value::<JobPayload>)
+            .collect::<Result<Vec<_>, _>>()?;
+        let results = process_batch(client, jobs).await;
+        return Ok(serde_json::to_value(results)?);
+    }
+
+    let job: JobPayload = serde_json::from_value(payload)?;
+    let response = process_job(client, job).await?;
     Ok(serde_json::to_value(response)?)
 }
 
@@ -1,18 +1,55 @@
+use aws_sdk_s3::{
+    config::{Builder as S3ConfigBuilder, Region},
+    presigning::PresigningConfig,
+    primitives::ByteStream,
+    Client as S3Client,
+};
 use axum::{
-    extract::{DefaultBodyLimit, Multipart},
-    http::StatusCode,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path as AxumPath},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
 use bambu_slicer::{slice_model, Slicer, SlicerConfig, SlicerStats};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
 use tempfile::TempDir;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::{OnceCell, Semaphore};
+use tokio_util::io::ReaderStream;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::{error, info};
 use uuid::Uuid;
 
+static S3_CLIENT: OnceCell<S3Client> = OnceCell::const_new();
+
+async fn s3_client() -> &'static S3Client {
+    S3_CLIENT
+        .get_or_init(|| async {
+            let shared_config = aws_config::load_from_env().await;
+            let mut builder = S3ConfigBuilder::from(&shared_config);
+
+            if let Ok(endpoint_url) = std::env::var("S3_ENDPOINT_URL") {
+                builder = builder.endpoint_url(endpoint_url);
+            }
+            if let Ok(region) = std::env::var("S3_REGION") {
+                builder = builder.region(Region::new(region));
+            }
+            if let Ok(force_path_style) = std::env::var("S3_FORCE_PATH_STYLE") {
+                builder =
+                    builder.force_path_style(force_path_style == "true" || force_path_style == "1");
+            }
+
+            S3Client::from_conf(builder.build())
+        })
+        .await
+}
+
 /// API error response
 #[derive(Debug, Serialize)]
 struct ErrorResponse {
@@ -21,19 +58,47 @@ struct ErrorResponse {
 }
 
 /// Slice request configuration
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize)]
 struct SliceRequest {
     /// Printer preset name (e.g., "Bambu Lab A1")
     printer_preset: Option<String>,
-    
+
     /// Filament preset name (e.g., "Bambu PLA Basic @BBL A1")
     filament_preset: Option<String>,
-    
+
     /// Process preset name (e.g., "0.20mm Standard @BBL A1")
     process_preset: Option<String>,
-    
+
     /// Custom parameters as key-value pairs
     custom_params: Option<Vec<(String, String)>>,
+
+    /// Return presigned S3 URLs instead of inlining the G-code as base64.
+    /// Requires `RESULT_S3_BUCKET` to be set. Defaults to `false`.
+    presign: Option<bool>,
+
+    /// How long the presigned URLs stay valid, in seconds. Defaults to 3600.
+    presign_ttl_secs: Option<u64>,
+
+    /// S3 location of the input model (`s3://bucket/key`), as an
+    /// alternative to uploading it as a multipart `model` field. When set and
+    /// no `model` field is uploaded (an uploaded model takes precedence), the
+    /// service downloads the model itself via a presigned GET rather
+    /// than requiring the client to proxy the file through.
+    input_s3_location: Option<String>,
+}
+
+/// The preset defaults used when a request omits its `config` field
+/// entirely.
+fn default_slice_request() -> SliceRequest {
+    SliceRequest {
+        printer_preset: Some("Bambu Lab A1".to_string()),
+        filament_preset: Some("Bambu PLA Basic @BBL A1".to_string()),
+        process_preset: Some("0.20mm Standard @BBL A1".to_string()),
+        custom_params: None,
+        presign: None,
+        presign_ttl_secs: None,
+        input_s3_location: None,
+    }
 }
 
 /// Slice response
@@ -41,12 +106,112 @@ struct SliceRequest {
 struct SliceResponse {
     /// Unique ID for this slicing job
     job_id: String,
-    
+
     /// Print statistics
     stats: SlicerStats,
-    
-    /// Base64-encoded G-code content
-    gcode: String,
+
+    /// Base64-encoded G-code content. Present unless `presign` was requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gcode: Option<String>,
+
+    /// Presigned URL for the G-code, present only in presigned mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gcode_url: Option<String>,
+
+    /// Presigned URL for the slicing metadata (stats as JSON), present only
+    /// in presigned mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata_url: Option<String>,
+}
+
+/// Status of an asynchronous job created via `POST /jobs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+/// State of an asynchronous job. Keeps the job's `TempDir` alive for as long
+/// as the job is tracked, since the G-code it produced lives inside it.
+struct Job {
+    status: JobStatus,
+    stats: Option<SlicerStats>,
+    error: Option<String>,
+    gcode_path: Option<PathBuf>,
+    _temp_dir: Arc<TempDir>,
+    /// When the job reached `Done`/`Failed`; used to evict it once it's
+    /// older than [`JOB_RETENTION`]. `None` while still queued/running.
+    finished_at: Option<Instant>,
+}
+
+/// How long a finished job (and the `TempDir` holding its model/G-code)
+/// stays in [`JOBS`] before it's evicted on the next `POST /jobs` or
+/// `GET /jobs/{id}` request.
+const JOB_RETENTION: Duration = Duration::from_secs(3600);
+
+static JOBS: OnceCell<Mutex<HashMap<String, Job>>> = OnceCell::const_new();
+
+async fn jobs() -> &'static Mutex<HashMap<String, Job>> {
+    JOBS.get_or_init(|| async { Mutex::new(HashMap::new()) })
+        .await
+}
+
+/// Lock [`JOBS`], recovering the guard if a prior holder panicked instead of
+/// poisoning every `/jobs*` endpoint for the rest of the process's
+/// life (the background slicing task runs FFI code, which can panic).
+fn lock_jobs(jobs: &Mutex<HashMap<String, Job>>) -> MutexGuard<'_, HashMap<String, Job>> {
+    jobs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Drop jobs that finished more than [`JOB_RETENTION`] ago, so memory and
+/// the `TempDir`s they hold open don't grow unbounded over the server's
+/// lifetime. Called opportunistically by `create_job` and `get_job`.
+fn evict_expired_jobs(guard: &mut HashMap<String, Job>) {
+    guard.retain(|_, job| match job.finished_at {
+        Some(finished_at) => finished_at.elapsed() < JOB_RETENTION,
+        None => true,
+    });
+}
+
+/// Response to `POST /jobs`.
+#[derive(Debug, Serialize)]
+struct JobCreatedResponse {
+    job_id: String,
+    status: JobStatus,
+}
+
+/// Response to `GET /jobs/{id}`.
+#[derive(Debug, Serialize)]
+struct JobStatusResponse {
+    job_id: String,
+    status: JobStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<SlicerStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Outcome of a single item in a `/slice/batch` request.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BatchItemStatus {
+    Success,
+    Error,
+}
+
+/// Per-item result of a `/slice/batch` request. A failing item reports its
+/// error here rather than aborting the rest of the batch.
+#[derive(Debug, Serialize)]
+struct BatchItemResult {
+    index: usize,
+    status: BatchItemStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<SlicerStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
 }
 
 /// Health check response
@@ -62,6 +227,8 @@ enum AppError {
     SlicerError(bambu_slicer::SlicerError),
     IoError(std::io::Error),
     InvalidRequest(String),
+    Internal(String),
+    NotFound(String),
 }
 
 impl IntoResponse for AppError {
@@ -82,6 +249,12 @@ impl IntoResponse for AppError {
                 "Invalid request".to_string(),
                 Some(msg),
             ),
+            AppError::Internal(msg) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal error".to_string(),
+                Some(msg),
+            ),
+            AppError::NotFound(msg) => (StatusCode::NOT_FOUND, "Not found".to_string(), Some(msg)),
         };
 
         let error_response = ErrorResponse {
@@ -115,20 +288,367 @@ async fn health() -> Json<HealthResponse> {
 }
 
 /// Main slicing endpoint
-/// 
+///
 /// Accepts multipart form data with:
 /// - `model`: STL/3MF/AMF/OBJ file
 /// - `config`: JSON configuration (optional)
-async fn slice(mut multipart: Multipart) -> Result<Json<SliceResponse>, AppError> {
+async fn slice(multipart: Multipart) -> Result<Json<SliceResponse>, AppError> {
     let job_id = Uuid::new_v4().to_string();
     info!("Starting slicing job: {}", job_id);
 
-    // Create temporary directory for this job
+    let (model_path, config, temp_dir) = parse_slice_multipart(multipart).await?;
+
+    // Perform slicing
+    info!("Starting slicing process");
+    let output_path = temp_dir.path().join("output.gcode");
+
+    let stats = if config.custom_params.is_some() {
+        // Use builder API for custom parameters
+        slice_with_custom_params(&model_path, &output_path, &config)?
+    } else {
+        // Use simple API for presets
+        slice_with_presets(&model_path, &output_path, &config)?
+    };
+
+    info!("Slicing completed successfully");
+    info!(
+        "Stats: time={}, filament={:.2}mm, weight={:.2}g",
+        stats.estimated_print_time, stats.total_used_filament, stats.total_weight
+    );
+
+    if config.presign.unwrap_or(false) {
+        let ttl_secs = config.presign_ttl_secs.unwrap_or(3600);
+        let (gcode_url, metadata_url) =
+            upload_and_presign(&job_id, &output_path, &stats, ttl_secs).await?;
+
+        Ok(Json(SliceResponse {
+            job_id,
+            stats,
+            gcode: None,
+            gcode_url: Some(gcode_url),
+            metadata_url: Some(metadata_url),
+        }))
+    } else {
+        // Read G-code and encode as base64
+        let gcode_bytes = std::fs::read(&output_path)?;
+        let gcode_base64 = base64_encode(&gcode_bytes);
+
+        Ok(Json(SliceResponse {
+            job_id,
+            stats,
+            gcode: Some(gcode_base64),
+            gcode_url: None,
+            metadata_url: None,
+        }))
+    }
+}
+
+/// Upload the sliced G-code and its stats as JSON metadata to
+/// `RESULT_S3_BUCKET` under a `{job_id}/` key prefix, then return presigned
+/// GET URLs for both, valid for `ttl_secs`.
+async fn upload_and_presign(
+    job_id: &str,
+    output_path: &Path,
+    stats: &SlicerStats,
+    ttl_secs: u64,
+) -> Result<(String, String), AppError> {
+    let bucket = std::env::var("RESULT_S3_BUCKET")
+        .map_err(|_| AppError::Internal("RESULT_S3_BUCKET is not set".to_string()))?;
+
+    let client = s3_client().await;
+    let gcode_key = format!("{}/output.gcode", job_id);
+    let metadata_key = format!("{}/metadata.json", job_id);
+
+    let gcode_body = ByteStream::from_path(output_path)
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to read G-code file: {}", e)))?;
+    client
+        .put_object()
+        .bucket(&bucket)
+        .key(&gcode_key)
+        .body(gcode_body)
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to upload G-code: {}", e)))?;
+
+    let metadata_json = serde_json::to_vec(stats)
+        .map_err(|e| AppError::Internal(format!("failed to serialize metadata: {}", e)))?;
+    client
+        .put_object()
+        .bucket(&bucket)
+        .key(&metadata_key)
+        .body(ByteStream::from(metadata_json))
+        .send()
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to upload metadata: {}", e)))?;
+
+    let presign_config = PresigningConfig::expires_in(Duration::from_secs(ttl_secs))
+        .map_err(|e| AppError::Internal(format!("invalid presign TTL: {}", e)))?;
+
+    let gcode_url = client
+        .get_object()
+        .bucket(&bucket)
+        .key(&gcode_key)
+        .presigned(presign_config.clone())
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to presign G-code URL: {}", e)))?
+        .uri()
+        .to_string();
+
+    let metadata_url = client
+        .get_object()
+        .bucket(&bucket)
+        .key(&metadata_key)
+        .presigned(presign_config)
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to presign metadata URL: {}", e)))?
+        .uri()
+        .to_string();
+
+    Ok((gcode_url, metadata_url))
+}
+
+/// Upper bound on slicer instances running concurrently for a single batch
+/// request, so a large batch can't exhaust `/tmp` or spawn unbounded slicer
+/// processes.
+const BATCH_CONCURRENCY_LIMIT: usize = 4;
+
+/// Batch slicing endpoint
+///
+/// Accepts multipart form data with one or more `model` fields (order
+/// determines `index` in the response), an optional shared `config` field,
+/// and optional per-item `config_{index}` fields that override it. Items are
+/// sliced concurrently, bounded by [`BATCH_CONCURRENCY_LIMIT`]; a failing
+/// item reports its error without aborting the rest of the batch.
+async fn slice_batch(mut multipart: Multipart) -> Result<Json<Vec<BatchItemResult>>, AppError> {
+    let temp_dir = TempDir::new()?;
+    let mut shared_config: Option<SliceRequest> = None;
+    let mut item_configs: HashMap<usize, SliceRequest> = HashMap::new();
+    let mut model_paths: Vec<PathBuf> = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| AppError::InvalidRequest(e.to_string()))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+
+        if name == "model" {
+            let filename = field
+                .file_name()
+                .ok_or_else(|| {
+                    AppError::InvalidRequest("Model file must have a filename".to_string())
+                })?
+                .to_string();
+
+            let extension = Path::new(&filename)
+                .extension()
+                .and_then(|e| e.to_str())
+                .ok_or_else(|| AppError::InvalidRequest("Invalid file extension".to_string()))?;
+
+            if !["stl", "3mf", "amf", "obj"].contains(&extension.to_lowercase().as_str()) {
+                return Err(AppError::InvalidRequest(
+                    "Unsupported file format. Use STL, 3MF, AMF, or OBJ".to_string(),
+                ));
+            }
+
+            let index = model_paths.len();
+            let file_path = temp_dir.path().join(format!("{}_{}", index, filename));
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
+            std::fs::write(&file_path, data)?;
+            model_paths.push(file_path);
+        } else if name == "config" {
+            let data = field
+                .text()
+                .await
+                .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
+            shared_config =
+                Some(serde_json::from_str(&data).map_err(|e| {
+                    AppError::InvalidRequest(format!("Invalid config JSON: {}", e))
+                })?);
+        } else if let Some(idx_str) = name.strip_prefix("config_") {
+            let index: usize = idx_str.parse().map_err(|_| {
+                AppError::InvalidRequest(format!("Invalid per-item config field name: {}", name))
+            })?;
+            let data = field
+                .text()
+                .await
+                .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
+            item_configs.insert(
+                index,
+                serde_json::from_str(&data)
+                    .map_err(|e| AppError::InvalidRequest(format!("Invalid config JSON: {}", e)))?,
+            );
+        } else {
+            info!("Ignoring unknown field: {}", name);
+        }
+    }
+
+    if model_paths.is_empty() {
+        return Err(AppError::InvalidRequest(
+            "No model files provided".to_string(),
+        ));
+    }
+
+    let default_config = default_slice_request;
+
+    let semaphore = Arc::new(Semaphore::new(BATCH_CONCURRENCY_LIMIT));
+    let mut tasks = Vec::with_capacity(model_paths.len());
+
+    for (index, model_path) in model_paths.into_iter().enumerate() {
+        let config = item_configs
+            .remove(&index)
+            .or_else(|| shared_config.clone())
+            .unwrap_or_else(default_config);
+        let semaphore = semaphore.clone();
+        let output_path = temp_dir.path().join(format!("{}_output.gcode", index));
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("batch semaphore was closed");
+
+            // The FFI slice call is blocking (and can take minutes), so it
+            // runs on a blocking-pool thread rather than tying up a tokio
+            // worker for its whole duration.
+            let result = tokio::task::spawn_blocking(move || {
+                if config.custom_params.is_some() {
+                    slice_with_custom_params(&model_path, &output_path, &config)
+                } else {
+                    slice_with_presets(&model_path, &output_path, &config)
+                }
+            })
+            .await;
+
+            match result {
+                Ok(Ok(stats)) => BatchItemResult {
+                    index,
+                    status: BatchItemStatus::Success,
+                    stats: Some(stats),
+                    error: None,
+                },
+                Ok(Err(e)) => BatchItemResult {
+                    index,
+                    status: BatchItemStatus::Error,
+                    stats: None,
+                    error: Some(app_error_message(e)),
+                },
+                Err(join_err) => BatchItemResult {
+                    index,
+                    status: BatchItemStatus::Error,
+                    stats: None,
+                    error: Some(format!("slicing task panicked: {}", join_err)),
+                },
+            }
+        }));
+    }
+
+    let mut results = Vec::with_capacity(tasks.len());
+    for (index, task) in tasks.into_iter().enumerate() {
+        match task.await {
+            Ok(result) => results.push(result),
+            Err(join_err) => results.push(BatchItemResult {
+                index,
+                status: BatchItemStatus::Error,
+                stats: None,
+                error: Some(format!("batch item task panicked: {}", join_err)),
+            }),
+        }
+    }
+    results.sort_by_key(|r| r.index);
+
+    Ok(Json(results))
+}
+
+/// Enqueue a slicing job and return immediately with its `job_id`.
+///
+/// Accepts the same multipart form as `/slice` (`model` + optional `config`).
+/// The slice runs in a background task; poll `GET /jobs/{id}` for status and
+/// `GET /jobs/{id}/gcode` for the output once it's `done`.
+async fn create_job(multipart: Multipart) -> Result<Json<JobCreatedResponse>, AppError> {
+    let job_id = Uuid::new_v4().to_string();
+    let (model_path, config, temp_dir) = parse_slice_multipart(multipart).await?;
+    let temp_dir = Arc::new(temp_dir);
+
+    {
+        let mut guard = lock_jobs(jobs().await);
+        evict_expired_jobs(&mut guard);
+        guard.insert(
+            job_id.clone(),
+            Job {
+                status: JobStatus::Queued,
+                stats: None,
+                error: None,
+                gcode_path: None,
+                _temp_dir: temp_dir.clone(),
+                finished_at: None,
+            },
+        );
+    }
+
+    let worker_job_id = job_id.clone();
+    tokio::spawn(async move {
+        {
+            let mut guard = lock_jobs(jobs().await);
+            if let Some(job) = guard.get_mut(&worker_job_id) {
+                job.status = JobStatus::Running;
+            }
+        }
+
+        let output_path = temp_dir.path().join("output.gcode");
+        // The FFI slice call is blocking (and can take minutes), so it runs
+        // on a blocking-pool thread rather than tying up a tokio worker for
+        // its whole duration.
+        let result = tokio::task::spawn_blocking(move || {
+            if config.custom_params.is_some() {
+                slice_with_custom_params(&model_path, &output_path, &config)
+            } else {
+                slice_with_presets(&model_path, &output_path, &config)
+            }
+            .map(|stats| (stats, output_path))
+        })
+        .await;
+
+        let mut guard = lock_jobs(jobs().await);
+        if let Some(job) = guard.get_mut(&worker_job_id) {
+            match result {
+                Ok(Ok((stats, output_path))) => {
+                    job.status = JobStatus::Done;
+                    job.stats = Some(stats);
+                    job.gcode_path = Some(output_path);
+                }
+                Ok(Err(e)) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(app_error_message(e));
+                }
+                Err(join_err) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(format!("slicing task panicked: {}", join_err));
+                }
+            }
+            job.finished_at = Some(Instant::now());
+        }
+    });
+
+    Ok(Json(JobCreatedResponse {
+        job_id,
+        status: JobStatus::Queued,
+    }))
+}
+
+/// Parses the `model` + optional `config` multipart fields shared by `/slice`
+/// and `/jobs`.
+async fn parse_slice_multipart(
+    mut multipart: Multipart,
+) -> Result<(PathBuf, SliceRequest, TempDir), AppError> {
     let temp_dir = TempDir::new()?;
     let mut model_path: Option<PathBuf> = None;
     let mut config: Option<SliceRequest> = None;
 
-    // Parse multipart form
     while let Some(field) = multipart
         .next_field()
         .await
@@ -138,17 +658,19 @@ async fn slice(mut multipart: Multipart) -> Result<Json<SliceResponse>, AppError
 
         match name.as_str() {
             "model" => {
-                // Get filename
                 let filename = field
                     .file_name()
-                    .ok_or_else(|| AppError::InvalidRequest("Model file must have a filename".to_string()))?
+                    .ok_or_else(|| {
+                        AppError::InvalidRequest("Model file must have a filename".to_string())
+                    })?
                     .to_string();
 
-                // Verify extension
                 let extension = Path::new(&filename)
                     .extension()
                     .and_then(|e| e.to_str())
-                    .ok_or_else(|| AppError::InvalidRequest("Invalid file extension".to_string()))?;
+                    .ok_or_else(|| {
+                        AppError::InvalidRequest("Invalid file extension".to_string())
+                    })?;
 
                 if !["stl", "3mf", "amf", "obj"].contains(&extension.to_lowercase().as_str()) {
                     return Err(AppError::InvalidRequest(
@@ -156,15 +678,12 @@ async fn slice(mut multipart: Multipart) -> Result<Json<SliceResponse>, AppError
                     ));
                 }
 
-                // Save file
                 let file_path = temp_dir.path().join(&filename);
                 let data = field
                     .bytes()
                     .await
                     .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
-                
                 std::fs::write(&file_path, data)?;
-                info!("Saved model file: {}", file_path.display());
                 model_path = Some(file_path);
             }
             "config" => {
@@ -172,12 +691,9 @@ async fn slice(mut multipart: Multipart) -> Result<Json<SliceResponse>, AppError
                     .text()
                     .await
                     .map_err(|e| AppError::InvalidRequest(e.to_string()))?;
-                
-                config = Some(
-                    serde_json::from_str(&data)
-                        .map_err(|e| AppError::InvalidRequest(format!("Invalid config JSON: {}", e)))?,
-                );
-                info!("Loaded configuration");
+                config = Some(serde_json::from_str(&data).map_err(|e| {
+                    AppError::InvalidRequest(format!("Invalid config JSON: {}", e))
+                })?);
             }
             _ => {
                 info!("Ignoring unknown field: {}", name);
@@ -185,48 +701,207 @@ async fn slice(mut multipart: Multipart) -> Result<Json<SliceResponse>, AppError
         }
     }
 
-    // Verify model was provided
-    let model_path = model_path
-        .ok_or_else(|| AppError::InvalidRequest("No model file provided".to_string()))?;
+    let config = config.unwrap_or_else(default_slice_request);
 
-    // Use default A1 config if none provided
-    let config = config.unwrap_or(SliceRequest {
-        printer_preset: Some("Bambu Lab A1".to_string()),
-        filament_preset: Some("Bambu PLA Basic @BBL A1".to_string()),
-        process_preset: Some("0.20mm Standard @BBL A1".to_string()),
-        custom_params: None,
-    });
+    let model_path = match (model_path, &config.input_s3_location) {
+        (Some(path), _) => path,
+        (None, Some(location)) => download_input_from_s3(location, &temp_dir).await?,
+        (None, None) => {
+            return Err(AppError::InvalidRequest(
+                "No model file or input_s3_location provided".to_string(),
+            ))
+        }
+    };
 
-    // Perform slicing
-    info!("Starting slicing process");
-    let output_path = temp_dir.path().join("output.gcode");
+    Ok((model_path, config, temp_dir))
+}
 
-    let stats = if config.custom_params.is_some() {
-        // Use builder API for custom parameters
-        slice_with_custom_params(&model_path, &output_path, &config)?
-    } else {
-        // Use simple API for presets
-        slice_with_presets(&model_path, &output_path, &config)?
-    };
+/// Split an `s3://bucket/key` location into its bucket and key. A location
+/// without the `s3://` prefix is treated as a bare key against the
+/// `S3_BUCKET` env var, mirroring `slicer_lambda`'s convention.
+fn parse_s3_location(location: &str) -> Result<(String, String), AppError> {
+    let trimmed = location.trim();
+    if let Some(stripped) = trimmed.strip_prefix("s3://") {
+        let mut parts = stripped.splitn(2, '/');
+        let bucket = parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| AppError::InvalidRequest("S3 location missing bucket".to_string()))?;
+        let key = parts
+            .next()
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| AppError::InvalidRequest("S3 location missing key".to_string()))?;
+        return Ok((bucket.to_string(), key.to_string()));
+    }
 
-    info!("Slicing completed successfully");
-    info!("Stats: time={}, filament={:.2}mm, weight={:.2}g",
-        stats.estimated_print_time,
-        stats.total_used_filament,
-        stats.total_weight
-    );
+    let bucket = std::env::var("S3_BUCKET").map_err(|_| {
+        AppError::InvalidRequest(
+            "S3_BUCKET env var must be set when using non-s3:// paths".to_string(),
+        )
+    })?;
+    Ok((bucket, trimmed.to_string()))
+}
+
+/// Download the model at `location` (an `s3://bucket/key` or bare key) into
+/// `temp_dir` by generating a presigned GET URL and fetching it, so the
+/// client only has to share a location rather than proxy the file through
+/// this service.
+async fn download_input_from_s3(location: &str, temp_dir: &TempDir) -> Result<PathBuf, AppError> {
+    let (bucket, key) = parse_s3_location(location)?;
+
+    let client = s3_client().await;
+    let presign_config = PresigningConfig::expires_in(Duration::from_secs(300))
+        .map_err(|e| AppError::Internal(format!("invalid presign TTL: {}", e)))?;
+    let presigned_url = client
+        .get_object()
+        .bucket(&bucket)
+        .key(&key)
+        .presigned(presign_config)
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to presign input URL: {}", e)))?
+        .uri()
+        .to_string();
+
+    let response = reqwest::get(presigned_url)
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to download input model: {}", e)))?
+        .error_for_status()
+        .map_err(|e| AppError::Internal(format!("failed to download input model: {}", e)))?;
 
-    // Read G-code and encode as base64
-    let gcode_bytes = std::fs::read(&output_path)?;
-    let gcode_base64 = base64_encode(&gcode_bytes);
+    let filename = Path::new(&key)
+        .file_name()
+        .ok_or_else(|| AppError::InvalidRequest("S3 key has no file name".to_string()))?;
+    let file_path = temp_dir.path().join(filename);
 
-    Ok(Json(SliceResponse {
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| AppError::Internal(format!("failed to read input model body: {}", e)))?;
+    std::fs::write(&file_path, bytes)?;
+
+    Ok(file_path)
+}
+
+/// Report the current status of a job created via `POST /jobs`.
+async fn get_job(AxumPath(job_id): AxumPath<String>) -> Result<Json<JobStatusResponse>, AppError> {
+    let mut guard = lock_jobs(jobs().await);
+    evict_expired_jobs(&mut guard);
+    let job = guard
+        .get(&job_id)
+        .ok_or_else(|| AppError::NotFound(format!("unknown job: {}", job_id)))?;
+
+    Ok(Json(JobStatusResponse {
         job_id,
-        stats,
-        gcode: gcode_base64,
+        status: job.status,
+        stats: job.stats.clone(),
+        error: job.error.clone(),
     }))
 }
 
+/// Stream a finished job's G-code, honoring a single-range `Range: bytes=`
+/// header with `206 Partial Content`; falls back to a full `200` response
+/// when no Range header is present.
+async fn job_gcode(
+    AxumPath(job_id): AxumPath<String>,
+    headers: HeaderMap,
+) -> Result<Response, AppError> {
+    let gcode_path = {
+        let guard = lock_jobs(jobs().await);
+        let job = guard
+            .get(&job_id)
+            .ok_or_else(|| AppError::NotFound(format!("unknown job: {}", job_id)))?;
+
+        match job.status {
+            JobStatus::Done => job
+                .gcode_path
+                .clone()
+                .expect("a done job always has a gcode_path"),
+            JobStatus::Failed => {
+                return Err(AppError::InvalidRequest(format!(
+                    "job failed: {}",
+                    job.error.clone().unwrap_or_default()
+                )))
+            }
+            JobStatus::Queued | JobStatus::Running => {
+                return Err(AppError::InvalidRequest(format!(
+                    "job not finished (status: {:?})",
+                    job.status
+                )))
+            }
+        }
+    };
+
+    let file_size = tokio::fs::metadata(&gcode_path).await?.len();
+    let mut file = tokio::fs::File::open(&gcode_path).await?;
+
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_range_header);
+
+    let Some((start, end)) = range else {
+        let body = Body::from_stream(ReaderStream::new(file));
+        return Response::builder()
+            .status(StatusCode::OK)
+            .header(header::ACCEPT_RANGES, "bytes")
+            .header(header::CONTENT_LENGTH, file_size.to_string())
+            .body(body)
+            .map_err(|e| AppError::Internal(e.to_string()));
+    };
+
+    let end = end.min(file_size.saturating_sub(1));
+    if file_size == 0 || start > end || start >= file_size {
+        return Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(header::CONTENT_RANGE, format!("bytes */{}", file_size))
+            .body(Body::empty())
+            .map_err(|e| AppError::Internal(e.to_string()));
+    }
+
+    let length = end - start + 1;
+    file.seek(std::io::SeekFrom::Start(start)).await?;
+    let body = Body::from_stream(ReaderStream::new(file.take(length)));
+
+    Response::builder()
+        .status(StatusCode::PARTIAL_CONTENT)
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(
+            header::CONTENT_RANGE,
+            format!("bytes {}-{}/{}", start, end, file_size),
+        )
+        .header(header::CONTENT_LENGTH, length.to_string())
+        .body(body)
+        .map_err(|e| AppError::Internal(e.to_string()))
+}
+
+/// Parses a single-range `Range: bytes=start-end` header value. An empty end
+/// (`bytes=500-`, meaning "to EOF") is returned as `u64::MAX` for the caller
+/// to clamp against the file size.
+fn parse_range_header(value: &str) -> Option<(u64, u64)> {
+    let spec = value.strip_prefix("bytes=")?;
+    let (start_str, end_str) = spec.split_once('-')?;
+    let start: u64 = start_str.parse().ok()?;
+    let end = if end_str.is_empty() {
+        u64::MAX
+    } else {
+        end_str.parse().ok()?
+    };
+    Some((start, end))
+}
+
+/// Flatten an [`AppError`] into a plain message, for embedding in a
+/// [`BatchItemResult`] rather than turning the whole batch response into an
+/// HTTP error.
+fn app_error_message(err: AppError) -> String {
+    match err {
+        AppError::SlicerError(e) => e.to_string(),
+        AppError::IoError(e) => e.to_string(),
+        AppError::InvalidRequest(msg) => msg,
+        AppError::Internal(msg) => msg,
+        AppError::NotFound(msg) => msg,
+    }
+}
+
 /// Slice using preset-based configuration (simple API)
 fn slice_with_presets(
     model_path: &Path,
@@ -276,7 +951,16 @@ fn slice_with_custom_params(
     }
 
     // Slice
-    let stats = slicer.slice()?;
+    let outcome = slicer.slice()?;
+
+    let Some(stats) = outcome.stats else {
+        return Err(AppError::SlicerError(bambu_slicer::SlicerError::Internal(
+            format!(
+                "Slicing did not produce statistics (status: {:?})",
+                outcome.status
+            ),
+        )));
+    };
 
     // Export
     slicer.export_gcode(output_path)?;
@@ -289,7 +973,10 @@ fn base64_encode(data: &[u8]) -> String {
     use std::io::Write;
     let mut output = Vec::new();
     {
-        let mut encoder = base64::write::EncoderWriter::new(&mut output, &base64::engine::general_purpose::STANDARD);
+        let mut encoder = base64::write::EncoderWriter::new(
+            &mut output,
+            &base64::engine::general_purpose::STANDARD,
+        );
         encoder.write_all(data).unwrap();
     }
     String::from_utf8(output).unwrap()
@@ -313,6 +1000,10 @@ async fn main() {
     let app = Router::new()
         .route("/health", get(health))
         .route("/slice", post(slice))
+        .route("/slice/batch", post(slice_batch))
+        .route("/jobs", post(create_job))
+        .route("/jobs/:id", get(get_job))
+        .route("/jobs/:id/gcode", get(job_gcode))
         .layer(CorsLayer::permissive())
         .layer(DefaultBodyLimit::max(100 * 1024 * 1024)) // 100MB max
         .layer(tower_http::trace::TraceLayer::new_for_http());
@@ -325,10 +1016,12 @@ async fn main() {
 
     info!("Server listening on http://{}", addr);
     info!("Endpoints:");
-    info!("  GET  /health - Health check");
-    info!("  POST /slice  - Slice a model");
+    info!("  GET  /health            - Health check");
+    info!("  POST /slice             - Slice a model");
+    info!("  POST /slice/batch       - Slice multiple models concurrently");
+    info!("  POST /jobs              - Enqueue an asynchronous slicing job");
+    info!("  GET  /jobs/:id          - Poll a job's status");
+    info!("  GET  /jobs/:id/gcode    - Download a finished job's G-code (Range-capable)");
 
-    axum::serve(listener, app)
-        .await
-        .expect("Server failed");
+    axum::serve(listener, app).await.expect("Server failed");
 }